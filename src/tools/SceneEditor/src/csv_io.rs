@@ -0,0 +1,173 @@
+use crate::scene_data::SceneConfig;
+use std::fs;
+use std::path::Path;
+
+/// Column order of exported files; imports look columns up by these names
+const CSV_COLUMNS: [&str; 11] = [
+    "name", "pos_x", "pos_y", "pos_z", "rot_yaw", "rot_pitch", "rot_roll", "scale_x", "scale_y", "scale_z", "enabled",
+];
+
+impl SceneConfig {
+    /// Write one row per object with its name, transform and enabled flag
+    ///
+    /// Floats are written in their shortest exact form, so importing the file
+    /// again restores the same values.
+    pub fn export_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        let mut out = CSV_COLUMNS.join(",");
+        out.push('\n');
+        for obj in &self.objects {
+            let t = &obj.transform;
+            let mut fields = vec![quote_field(&obj.name)];
+            fields.extend(t.position.iter().chain(&t.rotation_euler).chain(&t.scale).map(f32::to_string));
+            fields.push(obj.enabled.to_string());
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+        fs::write(path, out)?;
+        Ok(())
+    }
+
+    /// Update transforms and enabled flags of objects named in a CSV file
+    ///
+    /// Objects not in the file are left alone. Every row is checked before any
+    /// object changes, so a bad row leaves the scene untouched. Returns how many
+    /// objects were updated and how many rows named no existing object.
+    pub fn import_csv<P: AsRef<Path>>(&mut self, path: P) -> Result<(usize, usize), Box<dyn std::error::Error>> {
+        let text = fs::read_to_string(path)?;
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+
+        let header = split_line(lines.next().ok_or("file is empty")?);
+        let mut columns = [0; CSV_COLUMNS.len()];
+        for (column, name) in columns.iter_mut().zip(CSV_COLUMNS) {
+            *column = header
+                .iter()
+                .position(|h| h.trim() == name)
+                .ok_or_else(|| format!("missing column '{}'", name))?;
+        }
+
+        let mut rows = Vec::new();
+        let mut unmatched = 0;
+        for (row, line) in lines.enumerate() {
+            let fields = split_line(line);
+            let field = |i: usize| {
+                fields
+                    .get(columns[i])
+                    .map(|f| f.trim())
+                    .ok_or_else(|| format!("row {}: missing '{}'", row + 1, CSV_COLUMNS[i]))
+            };
+            let number = |i: usize| -> Result<f32, String> {
+                let value = field(i)?;
+                value
+                    .parse()
+                    .map_err(|_| format!("row {}: '{}' is not a number for '{}'", row + 1, value, CSV_COLUMNS[i]))
+            };
+
+            let Some(idx) = self.find_object(field(0)?) else {
+                unmatched += 1;
+                continue;
+            };
+            let values = (1..10).map(number).collect::<Result<Vec<_>, _>>()?;
+            let enabled = field(10)?;
+            let enabled = enabled
+                .parse()
+                .map_err(|_| format!("row {}: '{}' is not true/false for 'enabled'", row + 1, enabled))?;
+
+            rows.push((idx, values, enabled));
+        }
+
+        for (idx, values, enabled) in &rows {
+            let obj = &mut self.objects[*idx];
+            obj.transform.position.copy_from_slice(&values[0..3]);
+            obj.transform.rotation_euler.copy_from_slice(&values[3..6]);
+            obj.transform.scale.copy_from_slice(&values[6..9]);
+            obj.enabled = *enabled;
+        }
+        Ok((rows.len(), unmatched))
+    }
+}
+
+/// Quote a field if it contains a delimiter or quote
+fn quote_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split one CSV line, honoring double-quoted fields with `""` escapes
+fn split_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_round_trip() {
+        let mut config = SceneConfig::example();
+        config.objects[0].name = "BMW, \"M3\"".to_string();
+        config.objects[0].transform.position = [0.1, -2.5e-3, 1234.567];
+        config.objects[0].transform.rotation_euler = [33.333_332, -0.1, 179.9];
+        config.objects[1].enabled = false;
+
+        let path = std::env::temp_dir().join(format!("scene_editor_round_trip_{}.csv", std::process::id()));
+        config.export_csv(&path).unwrap();
+
+        let mut imported = config.clone();
+        for obj in &mut imported.objects {
+            obj.transform = Default::default();
+            obj.enabled = true;
+        }
+        let result = imported.import_csv(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap(), (config.objects.len(), 0));
+        for (original, copy) in config.objects.iter().zip(&imported.objects) {
+            assert_eq!(copy.transform.position, original.transform.position);
+            assert_eq!(copy.transform.rotation_euler, original.transform.rotation_euler);
+            assert_eq!(copy.transform.scale, original.transform.scale);
+            assert_eq!(copy.enabled, original.enabled);
+        }
+    }
+
+    #[test]
+    fn csv_bad_row_changes_nothing() {
+        let mut config = SceneConfig::example();
+        let first = config.objects[0].name.clone();
+        let second = config.objects[1].name.clone();
+        let csv = format!(
+            "{}\n{},1,2,3,0,0,0,1,1,1,true\n{},oops,0,0,0,0,0,1,1,1,true\n",
+            CSV_COLUMNS.join(","),
+            first,
+            second
+        );
+        let path = std::env::temp_dir().join(format!("scene_editor_bad_row_{}.csv", std::process::id()));
+        fs::write(&path, csv).unwrap();
+
+        let before = config.objects[0].transform.position;
+        let result = config.import_csv(&path);
+        fs::remove_file(&path).unwrap();
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("row 2"), "{}", err);
+        assert_eq!(config.objects[0].transform.position, before);
+    }
+}
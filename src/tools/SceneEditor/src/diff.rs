@@ -0,0 +1,140 @@
+use crate::scene_data::SceneConfig;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// One structural difference between two configs
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Object only in the newer config
+    Added(String),
+    /// Object only in the older config
+    Removed(String),
+    /// Object at the same list position under a different name
+    Renamed { from: String, to: String },
+    /// Value at a dotted path such as `objects.Car.transform.position`; `None` is unset
+    Field { path: String, from: Option<String>, to: Option<String> },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let or_unset = |value: &Option<String>| value.clone().unwrap_or_else(|| "(unset)".to_string());
+        match self {
+            Change::Added(name) => write!(f, "+ {} added", name),
+            Change::Removed(name) => write!(f, "− {} removed", name),
+            Change::Renamed { from, to } => write!(f, "✏ {} renamed to {}", from, to),
+            Change::Field { path, from, to } => write!(f, "{}: {} → {}", path, or_unset(from), or_unset(to)),
+        }
+    }
+}
+
+impl SceneConfig {
+    /// Changes that turn `self` into `other`, field by field
+    ///
+    /// Objects are matched by name. Objects left unmatched on both sides at the
+    /// same list position count as renamed and are compared field by field too.
+    pub fn diff(&self, other: &SceneConfig) -> Result<Vec<Change>, serde_json::Error> {
+        let mut from = serde_json::to_value(self)?;
+        let mut to = serde_json::to_value(other)?;
+        let mut changes = Vec::new();
+
+        let from_objects = take_objects(&mut from);
+        let to_objects = take_objects(&mut to);
+        diff_values("", &from, &to, &mut changes);
+
+        let name = |obj: &Value| obj["name"].as_str().unwrap_or_default().to_string();
+        let find = |objects: &[Value], wanted: &str| objects.iter().position(|obj| name(obj) == wanted);
+        let mut matched_to = vec![false; to_objects.len()];
+        let mut unmatched_from = Vec::new();
+        for (idx, obj) in from_objects.iter().enumerate() {
+            match find(&to_objects, &name(obj)) {
+                Some(other_idx) => {
+                    matched_to[other_idx] = true;
+                    diff_values(&format!("objects.{}", name(obj)), obj, &to_objects[other_idx], &mut changes);
+                }
+                None => unmatched_from.push(idx),
+            }
+        }
+
+        for idx in unmatched_from {
+            match to_objects.get(idx).filter(|_| !matched_to[idx]) {
+                Some(renamed) => {
+                    matched_to[idx] = true;
+                    let (old_name, new_name) = (name(&from_objects[idx]), name(renamed));
+                    changes.push(Change::Renamed { from: old_name, to: new_name.clone() });
+                    let (mut old, mut new) = (from_objects[idx].clone(), renamed.clone());
+                    old["name"] = Value::Null;
+                    new["name"] = Value::Null;
+                    diff_values(&format!("objects.{}", new_name), &old, &new, &mut changes);
+                }
+                None => changes.push(Change::Removed(name(&from_objects[idx]))),
+            }
+        }
+        for (idx, obj) in to_objects.iter().enumerate() {
+            if !matched_to[idx] {
+                changes.push(Change::Added(name(obj)));
+            }
+        }
+        Ok(changes)
+    }
+}
+
+/// Remove and return the `objects` array, leaving the rest of the config to compare as fields
+fn take_objects(config: &mut Value) -> Vec<Value> {
+    match config.as_object_mut().and_then(|map| map.remove("objects")) {
+        Some(Value::Array(objects)) => objects,
+        _ => Vec::new(),
+    }
+}
+
+/// Record differing leaves below `path`; arrays and scalars are compared whole
+fn diff_values(path: &str, from: &Value, to: &Value, changes: &mut Vec<Change>) {
+    if let (Value::Object(a), Value::Object(b)) = (from, to) {
+        let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+        for key in keys {
+            let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+            diff_values(&child, a.get(key).unwrap_or(&Value::Null), b.get(key).unwrap_or(&Value::Null), changes);
+        }
+    } else if from != to {
+        let show = |value: &Value| (!value.is_null()).then(|| value.to_string());
+        changes.push(Change::Field { path: path.to_string(), from: show(from), to: show(to) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_structural_changes() {
+        let before = SceneConfig::example();
+        assert!(before.diff(&before).unwrap().is_empty());
+
+        let mut after = before.clone();
+        after.camera.cockpit.fov = 90.0;
+        after.objects[0].transform.position = [1.0, 0.0, 0.0];
+        after.objects[1].name = "Ground".to_string();
+        after.objects[1].opacity = 0.5;
+        let mut added = after.objects[0].clone();
+        added.name = "Tree".to_string();
+        added.model_path = Some("tree.glb".to_string());
+        after.objects.push(added);
+
+        let changes = before.diff(&after).unwrap();
+        let field = |path: &str| changes.iter().find_map(|c| match c {
+            Change::Field { path: p, from, to } if p == path => Some((from.clone(), to.clone())),
+            _ => None,
+        });
+        assert_eq!(field("camera.cockpit.fov"), Some((Some("75.0".to_string()), Some("90.0".to_string()))));
+        assert_eq!(field("objects.BMW_Model.transform.position"), Some((Some("[0.0,0.0,0.0]".to_string()), Some("[1.0,0.0,0.0]".to_string()))));
+        assert!(changes.contains(&Change::Renamed { from: "Road_Surface".to_string(), to: "Ground".to_string() }));
+        assert!(field("objects.Ground.opacity").is_some());
+        assert!(changes.contains(&Change::Added("Tree".to_string())));
+        assert_eq!(changes.len(), 5, "{:#?}", changes);
+
+        // And the other way round
+        let reverse = after.diff(&before).unwrap();
+        assert!(reverse.contains(&Change::Removed("Tree".to_string())));
+        assert!(reverse.contains(&Change::Renamed { from: "Ground".to_string(), to: "Road_Surface".to_string() }));
+    }
+}
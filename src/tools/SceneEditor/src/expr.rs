@@ -0,0 +1,117 @@
+/// Evaluate a typed number or simple arithmetic expression such as `2*1.5` or `-(3 + 4) / 2`
+///
+/// Supports `+ - * /`, parentheses, unary signs and exponent notation. Spaces are
+/// allowed between tokens but not inside numbers, so `3 4` is rejected rather than
+/// read as 34. Returns None for anything else, including trailing garbage.
+pub fn eval_expression(text: &str) -> Option<f64> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+    };
+    let value = parser.expression()?;
+    parser.skip_whitespace();
+    (parser.pos == parser.chars.len()).then_some(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        loop {
+            if self.eat('*') {
+                value *= self.factor()?;
+            } else if self.eat('/') {
+                value /= self.factor()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Option<f64> {
+        if self.eat('-') {
+            return self.factor().map(|v| -v);
+        }
+        if self.eat('+') {
+            return self.factor();
+        }
+        if self.eat('(') {
+            let value = self.expression()?;
+            return self.eat(')').then_some(value);
+        }
+        self.number()
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            let exponent_sign = (c == '-' || c == '+') && matches!(self.chars.get(self.pos.wrapping_sub(1)), Some('e' | 'E'));
+            if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || (exponent_sign && self.pos > start) {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        self.chars[start..self.pos].iter().collect::<String>().parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_arithmetic() {
+        assert_eq!(eval_expression("2+3*4"), Some(14.0));
+        assert_eq!(eval_expression("--1"), Some(1.0));
+        assert_eq!(eval_expression("-(3 + 4) / 2"), Some(-3.5));
+        assert_eq!(eval_expression(" 2 * ( 1 + 3 ) "), Some(8.0));
+        assert_eq!(eval_expression("1e-3"), Some(1e-3));
+        assert_eq!(eval_expression("2E+2"), Some(200.0));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for text in ["", "2x", "1e", "(1+2", "1+2)", "3 4", "1 .5", "1e -3", "2 *"] {
+            assert_eq!(eval_expression(text), None, "{:?}", text);
+        }
+    }
+}
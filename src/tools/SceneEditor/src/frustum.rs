@@ -0,0 +1,65 @@
+use glam::{Mat4, Vec3, Vec4};
+
+/// The six clip planes of a view-projection, for skipping objects that cannot be seen
+pub struct Frustum {
+    /// Left, right, bottom, top, near, far; `xyz` is the unit inward normal, `w` the offset
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extract the planes from a view-projection matrix with a 0..1 depth range
+    ///
+    /// Each plane is a sum or difference of the matrix rows (Gribb & Hartmann),
+    /// normalized so plane distances are in world units.
+    pub fn from_view_projection(view_projection: Mat4) -> Self {
+        let [r0, r1, r2, r3] = [0, 1, 2, 3].map(|i| view_projection.row(i));
+        let planes = [r3 + r0, r3 - r0, r3 + r1, r3 - r1, r2, r3 - r2]
+            .map(|plane| plane / plane.truncate().length());
+        Self { planes }
+    }
+
+    /// Whether any part of the sphere may be inside; false only when it is fully outside a plane
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.planes.iter().all(|plane| plane.truncate().dot(center) + plane.w >= -radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera_frustum() -> Frustum {
+        // At the origin looking down -Z, 90° vertical FOV, square aspect
+        let projection = Mat4::perspective_rh(90f32.to_radians(), 1.0, 1.0, 100.0);
+        Frustum::from_view_projection(projection * Mat4::IDENTITY)
+    }
+
+    #[test]
+    fn planes_match_the_camera() {
+        let frustum = camera_frustum();
+        let expected = [
+            Vec4::new(1.0, 0.0, -1.0, 0.0) / 2f32.sqrt(),
+            Vec4::new(-1.0, 0.0, -1.0, 0.0) / 2f32.sqrt(),
+            Vec4::new(0.0, 1.0, -1.0, 0.0) / 2f32.sqrt(),
+            Vec4::new(0.0, -1.0, -1.0, 0.0) / 2f32.sqrt(),
+            Vec4::new(0.0, 0.0, -1.0, -1.0),
+            Vec4::new(0.0, 0.0, 1.0, 100.0),
+        ];
+        for (plane, expected) in frustum.planes.iter().zip(expected) {
+            // Offsets scale with the far distance, and so does their rounding
+            assert!(plane.abs_diff_eq(expected, 1e-3), "{:?} != {:?}", plane, expected);
+        }
+    }
+
+    #[test]
+    fn spheres_outside_are_rejected() {
+        let frustum = camera_frustum();
+        assert!(frustum.intersects_sphere(Vec3::new(0.0, 0.0, -10.0), 1.0));
+        // Behind the camera, past the far plane, and off to the left
+        assert!(!frustum.intersects_sphere(Vec3::new(0.0, 0.0, 10.0), 1.0));
+        assert!(!frustum.intersects_sphere(Vec3::new(0.0, 0.0, -150.0), 1.0));
+        assert!(!frustum.intersects_sphere(Vec3::new(-20.0, 0.0, -10.0), 1.0));
+        // Big enough to reach back in
+        assert!(frustum.intersects_sphere(Vec3::new(-20.0, 0.0, -10.0), 15.0));
+    }
+}
@@ -0,0 +1,317 @@
+use crate::scene_data::{SceneConfig, SceneObject, Transform};
+use glam::{Mat3, Mat4, Quat, Vec3};
+use gltf::json;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Node extras key holding an object's model path
+const MODEL_PATH_EXTRA: &str = "modelPath";
+
+/// Up axis convention of a glTF file; the editor itself is Y-up like glTF
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpAxis {
+    #[default]
+    Y,
+    Z,
+}
+
+impl UpAxis {
+    pub const ALL: [UpAxis; 2] = [UpAxis::Y, UpAxis::Z];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UpAxis::Y => "Y-up",
+            UpAxis::Z => "Z-up",
+        }
+    }
+
+    /// Rotation taking this convention's coordinates into Y-up
+    ///
+    /// Z-up is turned -90° about X: +Z (up) becomes +Y and +Y becomes -Z.
+    fn to_y_up(self) -> Quat {
+        match self {
+            UpAxis::Y => Quat::IDENTITY,
+            UpAxis::Z => Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+        }
+    }
+}
+
+/// Re-express a transform in another frame, `basis` mapping old axes onto new ones
+///
+/// The whole matrix is conjugated (basis * M * basis⁻¹), so meshes are assumed to
+/// use the same convention as the file. Position and rotation turn with the
+/// basis; scale components follow their axes.
+fn convert_transform(transform: &Transform, basis: Quat) -> Transform {
+    let axes = Mat3::from_quat(basis);
+    let scale = Vec3::from(transform.scale);
+    let mut converted = Transform {
+        position: (basis * Vec3::from(transform.position)).to_array(),
+        rotation_euler: [0.0; 3],
+        scale: [
+            axes.row(0).abs().dot(scale),
+            axes.row(1).abs().dot(scale),
+            axes.row(2).abs().dot(scale),
+        ],
+        pivot: (basis * Vec3::from(transform.pivot)).to_array(),
+        order: transform.order.clone(),
+    };
+    converted.set_rotation(basis * transform.rotation() * basis.inverse());
+    converted
+}
+
+impl SceneConfig {
+    /// Write each enabled object as a glTF node with its translation/rotation/scale
+    ///
+    /// Transforms are converted from Y-up into `up_axis`.
+    /// Parent links become node children. Disabled objects are left out, so their
+    /// children hang off the nearest exported ancestor with the transform relative
+    /// to it, keeping them where they are in the scene. glTF nodes cannot reference
+    /// meshes in other files, so an object's model path is stored in the node's extras.
+    pub fn export_gltf<P: AsRef<Path>>(&self, path: P, up_axis: UpAxis) -> Result<(), Box<dyn std::error::Error>> {
+        let basis = up_axis.to_y_up().inverse();
+        let mut root = json::Root {
+            asset: json::Asset {
+                generator: Some("DownPour Scene Editor".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Node index for every exported object
+        let mut node_of = HashMap::new();
+        for (idx, obj) in self.objects.iter().enumerate().filter(|(_, obj)| obj.enabled) {
+            let t = if self.exported_parent(idx) == self.parent_index(idx) {
+                convert_transform(&obj.transform, basis)
+            } else {
+                // Skipping a disabled parent: fold its transform in
+                let relative_to = self.exported_parent(idx).map_or(Mat4::IDENTITY, |parent| self.world_matrix(parent));
+                let (scale, rotation, translation) = (relative_to.inverse() * self.world_matrix(idx)).to_scale_rotation_translation();
+                let mut local = Transform {
+                    position: translation.to_array(),
+                    rotation_euler: [0.0; 3],
+                    scale: scale.to_array(),
+                    pivot: [0.0; 3],
+                    order: obj.transform.order.clone(),
+                };
+                local.set_rotation(rotation);
+                convert_transform(&local, basis)
+            };
+            let extras = match &obj.model_path {
+                Some(model_path) => {
+                    let value = serde_json::json!({ MODEL_PATH_EXTRA: model_path });
+                    Some(serde_json::value::to_raw_value(&value)?)
+                }
+                None => None,
+            };
+            let node = root.push(json::Node {
+                name: Some(obj.name.clone()),
+                // glTF has no pivot, so bake it into the translation
+                translation: Some((Vec3::from(t.position) + t.pivot_offset()).to_array()),
+                rotation: Some(json::scene::UnitQuaternion(t.rotation().to_array())),
+                scale: Some(t.scale),
+                extras,
+                ..Default::default()
+            });
+            node_of.insert(idx, node);
+        }
+
+        // Hook children onto exported parents; everything else is a scene root
+        let mut roots = Vec::new();
+        for idx in 0..self.objects.len() {
+            let Some(&node) = node_of.get(&idx) else {
+                continue;
+            };
+            match self.exported_parent(idx).and_then(|parent| node_of.get(&parent)) {
+                Some(&parent) => root.nodes[parent.value()]
+                    .children
+                    .get_or_insert_with(Vec::new)
+                    .push(node),
+                None => roots.push(node),
+            }
+        }
+
+        let scene = root.push(json::Scene {
+            extensions: None,
+            extras: None,
+            name: Some("Scene".to_string()),
+            nodes: roots,
+        });
+        root.scene = Some(scene);
+
+        fs::write(path, json::serialize::to_string_pretty(&root)?)?;
+        Ok(())
+    }
+
+    /// Nearest enabled ancestor, the node an exported object hangs off
+    fn exported_parent(&self, idx: usize) -> Option<usize> {
+        let mut current = self.parent_index(idx);
+        let mut steps = 0;
+        // Guard against hand-edited parent cycles like `world_matrix` does
+        while let Some(parent) = current.filter(|_| steps < self.objects.len()) {
+            if self.objects[parent].enabled {
+                return Some(parent);
+            }
+            current = self.parent_index(parent);
+            steps += 1;
+        }
+        None
+    }
+
+    /// Append an object for every node in a glTF file, returning how many were added
+    ///
+    /// Node names become object names (suffixed if already taken), parent/child links
+    /// are kept, and a `modelPath` in the node extras is restored. Transforms are
+    /// converted from `up_axis` into Y-up.
+    pub fn import_gltf_nodes<P: AsRef<Path>>(&mut self, path: P, up_axis: UpAxis) -> Result<usize, Box<dyn std::error::Error>> {
+        let gltf = gltf::Gltf::open(path)?;
+        let basis = up_axis.to_y_up();
+
+        let mut parent_of = HashMap::new();
+        for node in gltf.nodes() {
+            for child in node.children() {
+                parent_of.insert(child.index(), node.index());
+            }
+        }
+
+        let mut names = Vec::new();
+        for node in gltf.nodes() {
+            let base = node.name().map_or_else(|| format!("Node_{}", node.index()), str::to_string);
+            let name = self.unique_name(&base);
+            names.push(name.clone());
+
+            let (translation, rotation, scale) = node.transform().decomposed();
+            let mut transform = Transform {
+                position: translation,
+                rotation_euler: [0.0; 3],
+                scale,
+                ..Transform::default()
+            };
+            transform.set_rotation(Quat::from_array(rotation));
+            let transform = convert_transform(&transform, basis);
+
+            let model_path = node
+                .extras()
+                .as_ref()
+                .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw.get()).ok())
+                .and_then(|extras| extras.get(MODEL_PATH_EXTRA)?.as_str().map(str::to_string));
+
+            self.objects.push(SceneObject {
+                name,
+                transform,
+                enabled: true,
+                color: [1.0, 1.0, 1.0, 1.0],
+                opacity: 1.0,
+                parent: None,
+                model_path,
+                tags: Vec::new(),
+                instances: None,
+            });
+        }
+
+        // Objects are added as they are named so later nodes see earlier names as taken
+        let first = self.objects.len() - names.len();
+        for (index, obj) in self.objects[first..].iter_mut().enumerate() {
+            obj.parent = parent_of.get(&index).map(|&parent| names[parent].clone());
+        }
+
+        Ok(names.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_transform_eq(a: &Transform, b: &Transform) {
+        let close = |x: &[f32], y: &[f32], eps: f32| x.iter().zip(y).all(|(x, y)| (x - y).abs() < eps);
+        assert!(close(&a.position, &b.position, 1e-5), "{:?} != {:?}", a.position, b.position);
+        assert!(close(&a.rotation_euler, &b.rotation_euler, 1e-3), "{:?} != {:?}", a.rotation_euler, b.rotation_euler);
+        assert!(close(&a.scale, &b.scale, 1e-5), "{:?} != {:?}", a.scale, b.scale);
+    }
+
+    #[test]
+    fn z_up_conversion() {
+        let z_up = Transform {
+            position: [1.0, 2.0, 3.0],
+            rotation_euler: [0.0; 3],
+            scale: [1.0, 2.0, 3.0],
+            ..Transform::default()
+        };
+        let y_up = convert_transform(&z_up, UpAxis::Z.to_y_up());
+        assert_transform_eq(
+            &y_up,
+            &Transform {
+                position: [1.0, 3.0, -2.0],
+                rotation_euler: [0.0; 3],
+                scale: [1.0, 3.0, 2.0],
+                ..Transform::default()
+            },
+        );
+
+        // A Z-up turn about the up axis is a yaw once converted
+        let mut spin = Transform::default();
+        spin.set_rotation(Quat::from_rotation_z(0.5));
+        let converted = convert_transform(&spin, UpAxis::Z.to_y_up());
+        assert!(converted.rotation().abs_diff_eq(Quat::from_rotation_y(0.5), 1e-5));
+
+        // And back again
+        let original = Transform {
+            position: [4.0, -1.0, 0.5],
+            rotation_euler: [20.0, -35.0, 60.0],
+            scale: [1.0, 2.0, 3.0],
+            ..Transform::default()
+        };
+        let there = convert_transform(&original, UpAxis::Z.to_y_up());
+        let back = convert_transform(&there, UpAxis::Z.to_y_up().inverse());
+        assert_transform_eq(&back, &original);
+    }
+
+    #[test]
+    fn gltf_node_round_trip() {
+        let mut config = SceneConfig::example();
+        config.objects[0].transform.rotation_euler = [30.0, -20.0, 10.0];
+        config.objects[0].model_path = Some("bmw.glb".to_string());
+        config.objects[1].parent = Some(config.objects[0].name.clone());
+
+        for up_axis in UpAxis::ALL {
+            let path = std::env::temp_dir()
+                .join(format!("scene_editor_round_trip_{}_{:?}.gltf", std::process::id(), up_axis));
+            config.export_gltf(&path, up_axis).unwrap();
+
+            let mut imported = SceneConfig::blank();
+            let count = imported.import_gltf_nodes(&path, up_axis).unwrap();
+            fs::remove_file(&path).unwrap();
+
+            assert_eq!(count, config.objects.len());
+            for (original, copy) in config.objects.iter().zip(&imported.objects) {
+                assert_eq!(copy.name, original.name);
+                assert_eq!(copy.parent, original.parent);
+                assert_eq!(copy.model_path, original.model_path);
+                assert_transform_eq(&copy.transform, &original.transform);
+            }
+        }
+    }
+
+    #[test]
+    fn disabled_parent_keeps_child_in_place() {
+        let mut config = SceneConfig::example();
+        config.objects[0].enabled = false;
+        config.objects[0].transform.position = [2.0, 0.5, -1.0];
+        config.objects[0].transform.rotation_euler = [90.0, 0.0, 0.0];
+        config.objects[1].parent = Some(config.objects[0].name.clone());
+        config.objects[1].transform.position = [1.0, 0.0, 0.0];
+
+        let path = std::env::temp_dir().join(format!("scene_editor_disabled_parent_{}.gltf", std::process::id()));
+        config.export_gltf(&path, UpAxis::Y).unwrap();
+        let mut imported = SceneConfig::blank();
+        let count = imported.import_gltf_nodes(&path, UpAxis::Y).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(count, config.objects.len() - 1);
+        let child = imported.find_object(&config.objects[1].name).unwrap();
+        assert_eq!(imported.objects[child].parent, None);
+        let expected = config.world_matrix(1);
+        assert!(imported.world_matrix(child).abs_diff_eq(expected, 1e-5), "{:?} != {:?}", imported.world_matrix(child), expected);
+    }
+}
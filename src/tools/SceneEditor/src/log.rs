@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Oldest entries are dropped once the log grows past this
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    /// Prefix used in the status bar and log console
+    pub fn icon(self) -> &'static str {
+        match self {
+            LogLevel::Info => "✓",
+            LogLevel::Warning => "⚠",
+            LogLevel::Error => "✗",
+        }
+    }
+
+    /// Text color, picked to stay readable on both light and dark themes
+    pub fn color(self, visuals: &egui::Visuals) -> egui::Color32 {
+        match self {
+            LogLevel::Info if visuals.dark_mode => egui::Color32::GREEN,
+            LogLevel::Info => egui::Color32::DARK_GREEN,
+            LogLevel::Warning => visuals.warn_fg_color,
+            LogLevel::Error => visuals.error_fg_color,
+        }
+    }
+}
+
+pub struct LogEntry {
+    /// Time since the editor started
+    pub elapsed: Duration,
+    pub level: LogLevel,
+    pub text: String,
+}
+
+impl LogEntry {
+    /// `mm:ss.s` since the editor started
+    pub fn timestamp(&self) -> String {
+        let secs = self.elapsed.as_secs_f32();
+        format!("{:02}:{:04.1}", (secs / 60.0) as u32, secs % 60.0)
+    }
+}
+
+/// Messages collected while the editor runs, shown in the log console
+pub struct Log {
+    started: Instant,
+    entries: VecDeque<LogEntry>,
+}
+
+impl Default for Log {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            entries: VecDeque::new(),
+        }
+    }
+}
+
+impl Log {
+    pub fn push(&mut self, level: LogLevel, text: String) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            elapsed: self.started.elapsed(),
+            level,
+            text,
+        });
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -1,20 +1,77 @@
+mod csv_io;
+mod diff;
+mod expr;
+mod frustum;
+mod gltf_io;
+mod log;
+mod model;
 mod scene_data;
+mod session;
 mod ui;
+mod watcher;
 
 use eframe::{NativeOptions, egui::ViewportBuilder};
-use scene_data::SceneConfig;
-use ui::EditorState;
+use log::LogLevel;
+use scene_data::{autosave_path, SceneConfig};
+use session::EditorSession;
+use ui::{untitled_path, EditorState};
 use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 
 struct EditorApp {
     state: EditorState,
 }
 
 impl EditorApp {
-    fn new(config: SceneConfig, config_path: String) -> Self {
-        Self {
-            state: EditorState::new(config, config_path),
+    fn new(
+        config: SceneConfig,
+        config_path: String,
+        recovery: Option<PathBuf>,
+        session: Option<EditorSession>,
+        startup_messages: Vec<(LogLevel, String)>,
+    ) -> Self {
+        let mut state = EditorState::new(config, config_path);
+        state.recovery_path = recovery;
+        if let Some(session) = &session {
+            state.restore_session(session);
         }
+        for (level, text) in startup_messages {
+            state.log_message(level, text);
+        }
+        state.check_config();
+        Self { state }
+    }
+}
+
+/// Whether `path` exists and was modified after `than` (or `than` is missing)
+fn is_newer(path: &Path, than: &Path) -> bool {
+    let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) else {
+        return false;
+    };
+    match fs::metadata(than).and_then(|m| m.modified()) {
+        Ok(reference) => modified > reference,
+        Err(_) => true,
+    }
+}
+
+/// Whether a config argument is an http(s) URL rather than a file path
+fn is_url(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+/// Read the config named on the command line: a file path, `-` for stdin, or an http(s) URL
+fn load_config_source(source: &str) -> Result<SceneConfig, Box<dyn std::error::Error>> {
+    if source == "-" {
+        let mut json_str = String::new();
+        io::stdin().read_to_string(&mut json_str)?;
+        SceneConfig::from_json(&json_str)
+    } else if is_url(source) {
+        let json_str = ureq::get(source).call()?.into_string()?;
+        SceneConfig::from_json(&json_str)
+    } else {
+        SceneConfig::load(source)
     }
 }
 
@@ -28,28 +85,66 @@ fn main() -> Result<(), eframe::Error> {
     // Parse command line arguments
     let args: Vec<String> = env::args().collect();
 
+    // Print the config format for editors to validate against, then exit
+    if args.get(1).map(String::as_str) == Some("--emit-schema") {
+        let schema = schemars::schema_for!(SceneConfig);
+        match serde_json::to_string_pretty(&schema) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Could not write schema: {}", e),
+        }
+        return Ok(());
+    }
+
+    // Shown in the log console (and echoed to the terminal) once the editor starts
+    let mut startup_messages = Vec::new();
+
     let (config, config_path) = if args.len() > 1 {
-        let path = &args[1];
-        match SceneConfig::load(path) {
+        let source = &args[1];
+        let label = if source == "-" { "stdin" } else { source.as_str() };
+        match load_config_source(source) {
+            // Piped and downloaded configs have no file to save back to
+            Ok(cfg) if source == "-" || is_url(source) => {
+                let path = untitled_path("");
+                startup_messages.push((LogLevel::Info, format!("Loaded scene configuration from: {}", label)));
+                startup_messages.push((
+                    LogLevel::Warning,
+                    format!("Config was read from {}, not a local file; Save writes to {}", label, path),
+                ));
+                (cfg, path)
+            }
             Ok(cfg) => {
-                println!("Loaded scene configuration from: {}", path);
-                (cfg, path.clone())
+                startup_messages.push((LogLevel::Info, format!("Loaded scene configuration from: {}", source)));
+                (cfg, source.clone())
             }
             Err(e) => {
-                eprintln!("Error loading config from {}: {}", path, e);
-                eprintln!("Using example configuration instead.");
+                startup_messages.push((
+                    LogLevel::Error,
+                    format!("Error loading config from {}: {}. Using example configuration instead.", label, e),
+                ));
                 (SceneConfig::example(), "scene_config.json".to_string())
             }
         }
     } else {
-        println!("No config file specified, using example configuration.");
-        println!("Usage: {} [config.json]", args.get(0).unwrap_or(&"scene_editor".to_string()));
+        startup_messages.push((LogLevel::Info, "No config file specified, using example configuration.".to_string()));
+        println!("Usage: {} [config.json | - | http(s)://url | --emit-schema]", args.first().unwrap_or(&"scene_editor".to_string()));
         (SceneConfig::example(), "scene_config.json".to_string())
     };
 
+    // Offer to recover an autosave left behind by a crash or forced quit
+    let autosave = autosave_path(&config_path);
+    let recovery = is_newer(&autosave, Path::new(&config_path)).then_some(autosave);
+    if let Some(ref path) = recovery {
+        startup_messages.push((LogLevel::Warning, format!("Found autosave: {}", path.display())));
+    }
+
+    // Restore the working state from the last run on this config
+    let session = EditorSession::load_for(&config_path);
+    let window_size = session.as_ref().and_then(|s| s.window_size).unwrap_or([1400.0, 900.0]);
+    let theme = EditorSession::load_theme();
+
     let options = NativeOptions {
         viewport: ViewportBuilder::default()
-            .with_inner_size([1400.0, 900.0])
+            .with_inner_size(window_size)
             .with_title("DownPour Scene Editor"),
         ..Default::default()
     };
@@ -57,8 +152,11 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "DownPour Scene Editor",
         options,
-        Box::new(|_cc| {
-            Ok(Box::new(EditorApp::new(config, config_path)))
+        Box::new(move |cc| {
+            cc.egui_ctx.set_theme(theme.preference());
+            let mut app = EditorApp::new(config, config_path, recovery, session, startup_messages);
+            app.state.theme = theme;
+            Ok(Box::new(app))
         }),
     )
 }
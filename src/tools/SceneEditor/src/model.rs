@@ -0,0 +1,750 @@
+use glam::{Mat4, UVec3, Vec3};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+/// Error returned by a load that was canceled part way
+const CANCELED: &str = "canceled";
+
+/// CPU-side mesh data parsed from a model file
+///
+/// Positions are in model space with node transforms already applied.
+#[derive(Clone)]
+pub struct ModelData {
+    pub positions: Vec<[f32; 3]>,
+    pub indices: Vec<u32>,
+    pub bounds_min: Vec3,
+    pub bounds_max: Vec3,
+    /// Cached result of `bounding_sphere`, filled in by `with_bounding_sphere`
+    sphere: (Vec3, f32),
+}
+
+impl ModelData {
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Axis-aligned bounding box extents
+    pub fn size(&self) -> Vec3 {
+        self.bounds_max - self.bounds_min
+    }
+
+    /// Bounding box extent along X
+    pub fn width(&self) -> f32 {
+        self.size().x
+    }
+
+    /// Bounding box extent along Y
+    pub fn height(&self) -> f32 {
+        self.size().y
+    }
+
+    /// Bounding box extent along Z
+    pub fn depth(&self) -> f32 {
+        self.size().z
+    }
+
+    /// Sphere enclosing every vertex, as (center, radius) in model space
+    ///
+    /// Computed once when the model is built, so it is cheap to ask for every frame.
+    pub fn bounding_sphere(&self) -> (Vec3, f32) {
+        self.sphere
+    }
+
+    /// Compute and store the bounding sphere
+    ///
+    /// Uses Ritter's method: not the minimal sphere, but usually within a few
+    /// percent of it and tighter than the box's corner distance.
+    fn with_bounding_sphere(mut self) -> Self {
+        let points = self.positions.iter().map(|&p| Vec3::from(p));
+        let Some(first) = points.clone().next() else {
+            self.sphere = (Vec3::ZERO, 0.0);
+            return self;
+        };
+        let farthest_from = |from: Vec3| {
+            points
+                .clone()
+                .max_by(|a, b| from.distance_squared(*a).total_cmp(&from.distance_squared(*b)))
+                .unwrap_or(from)
+        };
+
+        // Start from a rough diameter, then grow to take in any point left outside
+        let a = farthest_from(first);
+        let b = farthest_from(a);
+        let mut center = (a + b) / 2.0;
+        let mut radius = a.distance(b) / 2.0;
+        for p in points {
+            let distance = center.distance(p);
+            if distance > radius {
+                let grown = (radius + distance) / 2.0;
+                center += (p - center) * ((grown - radius) / distance);
+                radius = grown;
+            }
+        }
+        self.sphere = (center, radius);
+        self
+    }
+
+    /// Bounding sphere after applying `matrix`; non-uniform scale grows the radius by the largest axis
+    pub fn transformed_bounding_sphere(&self, matrix: Mat4) -> (Vec3, f32) {
+        let (center, radius) = self.bounding_sphere();
+        let stretch = matrix.x_axis.truncate().length()
+            .max(matrix.y_axis.truncate().length())
+            .max(matrix.z_axis.truncate().length());
+        (matrix.transform_point3(center), radius * stretch)
+    }
+
+    /// Copy reduced to at most `ratio` of the triangles, for previewing heavy meshes
+    ///
+    /// Uses vertex clustering: vertices are merged per cell of a grid over the
+    /// bounding box, and triangles that collapse are dropped. The finest grid that
+    /// meets the target is found by bisecting the cell count. Coarse next to
+    /// quadric simplification, but fast and keeps the overall shape.
+    pub fn simplified(&self, ratio: f32) -> ModelData {
+        let target = (self.triangle_count() as f32 * ratio.clamp(0.0, 1.0)) as usize;
+        if target >= self.triangle_count() {
+            return self.clone();
+        }
+
+        let (mut low, mut high) = (1, 1025);
+        let mut best = self.clustered(low);
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            let candidate = self.clustered(mid);
+            if candidate.triangle_count() <= target {
+                low = mid;
+                best = candidate;
+            } else {
+                high = mid;
+            }
+        }
+        best
+    }
+
+    /// Merge vertices sharing a cell of a `cells`³ grid, at their average position
+    fn clustered(&self, cells: u32) -> ModelData {
+        let size = self.size().max(Vec3::splat(f32::EPSILON));
+        let mut cluster_of = HashMap::new();
+        let mut sums: Vec<(Vec3, f32)> = Vec::new();
+        let remap: Vec<u32> = self
+            .positions
+            .iter()
+            .map(|&p| {
+                let p = Vec3::from(p);
+                let cell = ((p - self.bounds_min) / size * cells as f32).as_uvec3().min(UVec3::splat(cells - 1));
+                let cluster = *cluster_of.entry(cell).or_insert_with(|| {
+                    sums.push((Vec3::ZERO, 0.0));
+                    sums.len() as u32 - 1
+                });
+                sums[cluster as usize].0 += p;
+                sums[cluster as usize].1 += 1.0;
+                cluster
+            })
+            .collect();
+
+        // Drop collapsed triangles and the duplicates merging leaves behind, keeping winding
+        let mut seen = HashSet::new();
+        let mut indices = Vec::new();
+        for tri in self.indices.chunks_exact(3) {
+            let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| remap[i as usize]);
+            if a == b || b == c || a == c {
+                continue;
+            }
+            let key = if a < b && a < c { [a, b, c] } else if b < c { [b, c, a] } else { [c, a, b] };
+            if seen.insert(key) {
+                indices.extend(key);
+            }
+        }
+
+        let positions: Vec<[f32; 3]> = sums.iter().map(|&(sum, count)| (sum / count).to_array()).collect();
+        ModelData {
+            bounds_min: positions.iter().fold(Vec3::INFINITY, |m, &p| m.min(Vec3::from(p))),
+            bounds_max: positions.iter().fold(Vec3::NEG_INFINITY, |m, &p| m.max(Vec3::from(p))),
+            positions,
+            indices,
+            sphere: (Vec3::ZERO, 0.0),
+        }
+        .with_bounding_sphere()
+    }
+}
+
+/// Progress of a model load, shared between the loader thread and the editor
+#[derive(Default)]
+pub struct LoadProgress {
+    /// Mesh primitives processed so far
+    done: AtomicUsize,
+    /// Mesh primitives to process; 0 until known
+    total: AtomicUsize,
+    canceled: AtomicBool,
+}
+
+impl LoadProgress {
+    /// Fraction done, once the amount of work is known
+    pub fn fraction(&self) -> Option<f32> {
+        let total = self.total.load(Ordering::Relaxed);
+        (total > 0).then(|| (self.done.load(Ordering::Relaxed) as f32 / total as f32).min(1.0))
+    }
+
+    /// Ask the loader to stop at its next checkpoint
+    pub fn cancel(&self) {
+        self.canceled.store(true, Ordering::Relaxed);
+    }
+
+    fn check_canceled(&self) -> Result<(), String> {
+        if self.canceled.load(Ordering::Relaxed) {
+            Err(CANCELED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Parse a model file, choosing the loader from its extension
+///
+/// glTF loads report per-primitive progress and stop early once canceled;
+/// .obj and .stl files are parsed in one step.
+pub fn load_model(path: &Path, progress: &LoadProgress) -> Result<ModelData, String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    let model = match extension.as_str() {
+        "gltf" | "glb" => load_gltf(path, progress),
+        "obj" => load_obj(path),
+        "stl" => load_stl(path),
+        "" => Err("model file has no extension (expected .gltf, .glb, .obj or .stl)".to_string()),
+        other => Err(format!("unsupported model format '.{}' (expected .gltf, .glb, .obj or .stl)", other)),
+    };
+    model.map(ModelData::with_bounding_sphere)
+}
+
+/// Parse all triangle primitives of a glTF/glb file
+fn load_gltf(path: &Path, progress: &LoadProgress) -> Result<ModelData, String> {
+    let gltf = gltf::Gltf::open(path).map_err(|e| e.to_string())?;
+    progress.check_canceled()?;
+    let buffers = gltf::import_buffers(&gltf.document, path.parent(), gltf.blob.clone())
+        .map_err(|e| e.to_string())?;
+    progress.check_canceled()?;
+
+    let mut model = ModelData {
+        positions: Vec::new(),
+        indices: Vec::new(),
+        bounds_min: Vec3::splat(f32::INFINITY),
+        bounds_max: Vec3::splat(f32::NEG_INFINITY),
+        sphere: (Vec3::ZERO, 0.0),
+    };
+
+    // Gather the meshes to draw first so the amount of work is known up front
+    let mut meshes = Vec::new();
+    match gltf.document.default_scene().or_else(|| gltf.document.scenes().next()) {
+        Some(scene) => {
+            for node in scene.nodes() {
+                collect_meshes(&mut meshes, &node, Mat4::IDENTITY);
+            }
+        }
+        None => meshes.extend(gltf.document.meshes().map(|mesh| (mesh, Mat4::IDENTITY))),
+    }
+    let total = meshes.iter().map(|(mesh, _)| mesh.primitives().len()).sum();
+    progress.total.store(total, Ordering::Relaxed);
+
+    for (mesh, world) in &meshes {
+        append_mesh(&mut model, mesh, *world, &buffers, progress)?;
+    }
+
+    if model.positions.is_empty() {
+        return Err("no triangle meshes found".to_string());
+    }
+    Ok(model)
+}
+
+/// Parse all meshes of a Wavefront .obj file, triangulating polygons
+fn load_obj(path: &Path) -> Result<ModelData, String> {
+    let options = tobj::LoadOptions {
+        triangulate: true,
+        single_index: true,
+        ..Default::default()
+    };
+    // Materials only affect shading, so a missing .mtl is not an error here
+    let (meshes, _materials) = tobj::load_obj(path, &options).map_err(|e| e.to_string())?;
+
+    let mut model = ModelData {
+        positions: Vec::new(),
+        indices: Vec::new(),
+        bounds_min: Vec3::splat(f32::INFINITY),
+        bounds_max: Vec3::splat(f32::NEG_INFINITY),
+        sphere: (Vec3::ZERO, 0.0),
+    };
+
+    for mesh in meshes.iter().map(|m| &m.mesh) {
+        let base = model.positions.len() as u32;
+        for p in mesh.positions.chunks_exact(3) {
+            let p = Vec3::new(p[0], p[1], p[2]);
+            model.bounds_min = model.bounds_min.min(p);
+            model.bounds_max = model.bounds_max.max(p);
+            model.positions.push(p.to_array());
+        }
+        model.indices.extend(mesh.indices.iter().map(|i| base + i));
+    }
+
+    if model.indices.is_empty() {
+        return Err("no faces found".to_string());
+    }
+    Ok(model)
+}
+
+/// Parse a binary or ASCII STL file; stl_io welds shared corners into indexed vertices
+fn load_stl(path: &Path) -> Result<ModelData, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mesh = stl_io::read_stl(&mut file).map_err(|e| e.to_string())?;
+    if mesh.faces.is_empty() {
+        return Err("no facets found".to_string());
+    }
+
+    let positions: Vec<[f32; 3]> = mesh.vertices.into_iter().map(<[f32; 3]>::from).collect();
+    let (bounds_min, bounds_max) = positions.iter().fold(
+        (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+        |(min, max), &p| (min.min(Vec3::from(p)), max.max(Vec3::from(p))),
+    );
+    let indices = mesh.faces.iter().flat_map(|face| face.vertices.map(|i| i as u32)).collect();
+    Ok(ModelData { positions, indices, bounds_min, bounds_max, sphere: (Vec3::ZERO, 0.0) })
+}
+
+fn collect_meshes<'a>(meshes: &mut Vec<(gltf::Mesh<'a>, Mat4)>, node: &gltf::Node<'a>, parent: Mat4) {
+    let world = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+    if let Some(mesh) = node.mesh() {
+        meshes.push((mesh, world));
+    }
+    for child in node.children() {
+        collect_meshes(meshes, &child, world);
+    }
+}
+
+fn append_mesh(
+    model: &mut ModelData,
+    mesh: &gltf::Mesh,
+    world: Mat4,
+    buffers: &[gltf::buffer::Data],
+    progress: &LoadProgress,
+) -> Result<(), String> {
+    for primitive in mesh.primitives() {
+        progress.check_canceled()?;
+        progress.done.fetch_add(1, Ordering::Relaxed);
+        if primitive.mode() != gltf::mesh::Mode::Triangles {
+            continue;
+        }
+        let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+        let Some(positions) = reader.read_positions() else {
+            continue;
+        };
+
+        let base = model.positions.len() as u32;
+        for p in positions {
+            let p = world.transform_point3(Vec3::from(p));
+            model.bounds_min = model.bounds_min.min(p);
+            model.bounds_max = model.bounds_max.max(p);
+            model.positions.push(p.to_array());
+        }
+        let count = model.positions.len() as u32 - base;
+
+        match reader.read_indices() {
+            Some(indices) => {
+                for i in indices.into_u32() {
+                    // Checked here so a malformed file fails to load instead of panicking later
+                    if i >= count {
+                        return Err(format!(
+                            "mesh '{}' refers to vertex {} but its primitive has only {}",
+                            mesh.name().unwrap_or("unnamed"),
+                            i,
+                            count
+                        ));
+                    }
+                    model.indices.push(base + i);
+                }
+            }
+            None => model.indices.extend(base..base + count),
+        }
+    }
+    Ok(())
+}
+
+/// Resolve an object's model path; relative paths are relative to the config file
+pub fn resolve_model_path(config_path: &str, model_path: &str) -> PathBuf {
+    let path = Path::new(model_path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    match Path::new(config_path).parent() {
+        Some(dir) => dir.join(path),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Path to store for a model file: relative to the config's directory when inside it
+pub fn relative_model_path(config_path: &str, model_path: &Path) -> String {
+    let dir = Path::new(config_path).parent().and_then(|dir| {
+        let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        dir.canonicalize().ok()
+    });
+    let model = model_path.canonicalize().unwrap_or_else(|_| model_path.to_path_buf());
+    let relative = dir.as_deref().and_then(|dir| model.strip_prefix(dir).ok());
+    // Forward slashes keep configs portable between platforms
+    relative.unwrap_or(&model).to_string_lossy().replace('\\', "/")
+}
+
+struct PendingLoad {
+    path: PathBuf,
+    receiver: Receiver<Result<ModelData, String>>,
+    progress: Arc<LoadProgress>,
+}
+
+/// Simplified copy being built in the background
+struct PendingPreview {
+    path: PathBuf,
+    percent: u32,
+    receiver: Receiver<ModelData>,
+}
+
+/// Models parsed so far, keyed by resolved path, plus at most one load in flight
+#[derive(Default)]
+pub struct ModelCache {
+    /// Shared so preview threads can read a model while the editor keeps using it
+    models: HashMap<PathBuf, Arc<ModelData>>,
+    /// Simplified copies for the viewport, with the percentage of triangles they keep
+    previews: HashMap<PathBuf, (u32, ModelData)>,
+    pending_preview: Option<PendingPreview>,
+    /// Loads that failed or were canceled; not retried until cleared
+    failed: HashSet<PathBuf>,
+    pending: Option<PendingLoad>,
+}
+
+impl ModelCache {
+    pub fn get(&self, path: &Path) -> Option<&ModelData> {
+        self.models.get(path).map(Arc::as_ref)
+    }
+
+    /// Simplified copy of a loaded model keeping about `ratio` of its triangles
+    ///
+    /// Simplifying a heavy mesh takes a while, so it happens on a background
+    /// thread and this returns None until the copy is ready; one copy is built at
+    /// a time. The full model stays cached, so switching back to it is instant.
+    pub fn preview(&mut self, path: &Path, ratio: f32, ctx: &egui::Context) -> Option<&ModelData> {
+        let percent = (ratio * 100.0).round() as u32;
+        if let Some(pending) = &self.pending_preview {
+            match pending.receiver.try_recv() {
+                Ok(preview) => {
+                    let pending = self.pending_preview.take()?;
+                    self.previews.insert(pending.path, (pending.percent, preview));
+                }
+                Err(TryRecvError::Disconnected) => self.pending_preview = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        let model = self.models.get(path)?;
+        if matches!(self.previews.get(path), Some((built, _)) if *built == percent) {
+            return self.previews.get(path).map(|(_, preview)| preview);
+        }
+        if self.pending_preview.is_none() {
+            let (sender, receiver) = mpsc::channel();
+            let model = Arc::clone(model);
+            let ctx = ctx.clone();
+            thread::spawn(move || {
+                let _ = sender.send(model.simplified(percent as f32 / 100.0));
+                ctx.request_repaint();
+            });
+            self.pending_preview = Some(PendingPreview { path: path.to_path_buf(), percent, receiver });
+        }
+        None
+    }
+
+    /// Paths of all successfully loaded models, sorted
+    pub fn loaded_paths(&self) -> Vec<&Path> {
+        let mut paths: Vec<&Path> = self.models.keys().map(PathBuf::as_path).collect();
+        paths.sort();
+        paths
+    }
+
+    /// Path of the model currently being parsed
+    pub fn loading(&self) -> Option<&Path> {
+        self.pending.as_ref().map(|pending| pending.path.as_path())
+    }
+
+    /// Fraction of the current load done, when the loader can tell
+    pub fn progress(&self) -> Option<f32> {
+        self.pending.as_ref()?.progress.fraction()
+    }
+
+    /// Stop the load in flight, returning its path
+    ///
+    /// Already cached models are untouched. The canceled path is treated as
+    /// failed, so it is not requested again until failures are cleared.
+    pub fn cancel(&mut self) -> Option<PathBuf> {
+        let pending = self.pending.take()?;
+        pending.progress.cancel();
+        self.failed.insert(pending.path.clone());
+        Some(pending.path)
+    }
+
+    /// Start parsing `path` on a background thread unless it is cached, failed, or another load is running
+    pub fn request(&mut self, path: PathBuf, ctx: &egui::Context) {
+        if self.pending.is_some() || self.models.contains_key(&path) || self.failed.contains(&path) {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let progress = Arc::new(LoadProgress::default());
+        let thread_progress = Arc::clone(&progress);
+        let thread_path = path.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            // The editor may have dropped the receiver; nothing to report then
+            let _ = sender.send(load_model(&thread_path, &thread_progress));
+            ctx.request_repaint();
+        });
+        self.pending = Some(PendingLoad { path, receiver, progress });
+    }
+
+    /// Collect a finished background load, returning its path and outcome
+    pub fn poll(&mut self) -> Option<(PathBuf, Result<(), String>)> {
+        let pending = self.pending.as_ref()?;
+        let result = match pending.receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("loader thread exited unexpectedly".to_string()),
+        };
+
+        let path = self.pending.take()?.path;
+        match result {
+            Ok(model) => {
+                self.models.insert(path.clone(), Arc::new(model));
+                Some((path, Ok(())))
+            }
+            Err(e) => {
+                self.failed.insert(path.clone());
+                Some((path, Err(e)))
+            }
+        }
+    }
+
+    /// Forget failed loads so they are retried
+    pub fn clear_failures(&mut self) {
+        self.failed.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CUBE_OBJ: &str = "\
+v -1 -1 -1
+v 1 -1 -1
+v 1 1 -1
+v -1 1 -1
+v -1 -1 1
+v 1 -1 1
+v 1 1 1
+v -1 1 1
+f 1 4 3 2
+f 5 6 7 8
+f 1 2 6 5
+f 2 3 7 6
+f 3 4 8 7
+f 4 1 5 8
+";
+
+    #[test]
+    fn obj_cube_loads() {
+        let path = std::env::temp_dir().join(format!("scene_editor_cube_{}.obj", std::process::id()));
+        std::fs::write(&path, CUBE_OBJ).unwrap();
+        let model = load_model(&path, &LoadProgress::default());
+        let _ = std::fs::remove_file(&path);
+        let model = model.unwrap();
+
+        assert_eq!(model.vertex_count(), 8);
+        assert_eq!(model.triangle_count(), 12);
+        assert_eq!(model.bounds_min, Vec3::splat(-1.0));
+        assert_eq!(model.size(), Vec3::splat(2.0));
+    }
+
+    #[test]
+    fn stl_cube_loads() {
+        // Write the .obj cube's triangles as a binary STL
+        let obj_path = std::env::temp_dir().join(format!("scene_editor_stl_source_{}.obj", std::process::id()));
+        std::fs::write(&obj_path, CUBE_OBJ).unwrap();
+        let cube = load_model(&obj_path, &LoadProgress::default());
+        let _ = std::fs::remove_file(&obj_path);
+        let cube = cube.unwrap();
+        let triangles: Vec<stl_io::Triangle> = cube
+            .indices
+            .chunks_exact(3)
+            .map(|tri| stl_io::Triangle {
+                normal: stl_io::Normal::new([0.0; 3]),
+                vertices: [0, 1, 2].map(|i| stl_io::Vertex::new(cube.positions[tri[i] as usize])),
+            })
+            .collect();
+
+        let path = std::env::temp_dir().join(format!("scene_editor_cube_{}.stl", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        stl_io::write_stl(&mut file, triangles.iter()).unwrap();
+        drop(file);
+        let model = load_model(&path, &LoadProgress::default());
+        let _ = std::fs::remove_file(&path);
+        let model = model.unwrap();
+
+        assert_eq!(model.triangle_count(), 12);
+        // Corners shared between facets are welded
+        assert_eq!(model.vertex_count(), 8);
+        assert_eq!(model.size(), Vec3::splat(2.0));
+
+        // ASCII files load too
+        let ascii = "solid tri\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\nendloop\nendfacet\nendsolid tri\n";
+        let path = std::env::temp_dir().join(format!("scene_editor_tri_{}.stl", std::process::id()));
+        std::fs::write(&path, ascii).unwrap();
+        let model = load_model(&path, &LoadProgress::default());
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(model.unwrap().triangle_count(), 1);
+    }
+
+    #[test]
+    fn bounding_sphere_encloses_vertices() {
+        // A lopsided cloud: a long diagonal plus points scattered around it
+        let positions: Vec<[f32; 3]> = (0..200)
+            .map(|i| {
+                let t = i as f32;
+                [(t * 0.37).sin() * 3.0 + t * 0.02, (t * 1.3).cos() * 0.5, (t * 0.71).sin() * 1.5 - 2.0]
+            })
+            .collect();
+        let model = ModelData {
+            bounds_min: positions.iter().fold(Vec3::INFINITY, |m, &p| m.min(Vec3::from(p))),
+            bounds_max: positions.iter().fold(Vec3::NEG_INFINITY, |m, &p| m.max(Vec3::from(p))),
+            positions,
+            indices: Vec::new(),
+            sphere: (Vec3::ZERO, 0.0),
+        }
+        .with_bounding_sphere();
+
+        let (center, radius) = model.bounding_sphere();
+        for &p in &model.positions {
+            assert!(center.distance(Vec3::from(p)) <= radius + 1e-4, "{:?} outside r = {}", p, radius);
+        }
+        // Never looser than the sphere around the box corners
+        assert!(radius <= model.size().length() / 2.0 + 1e-4);
+
+        let matrix = Mat4::from_scale_rotation_translation(
+            Vec3::new(2.0, 0.5, 1.0),
+            glam::Quat::from_rotation_y(0.6),
+            Vec3::new(5.0, 0.0, -1.0),
+        );
+        let (center, radius) = model.transformed_bounding_sphere(matrix);
+        for &p in &model.positions {
+            let p = matrix.transform_point3(Vec3::from(p));
+            assert!(center.distance(p) <= radius + 1e-4, "{:?} outside r = {}", p, radius);
+        }
+    }
+
+    #[test]
+    fn simplified_meets_target() {
+        // A 40 × 40 quad height field: 3200 triangles
+        let n = 41;
+        let positions: Vec<[f32; 3]> = (0..n * n)
+            .map(|i| {
+                let (x, z) = ((i % n) as f32 / 4.0, (i / n) as f32 / 4.0);
+                [x, (x * 0.7).sin() * (z * 0.4).cos(), z]
+            })
+            .collect();
+        let mut indices = Vec::new();
+        for z in 0..n - 1 {
+            for x in 0..n - 1 {
+                let i = (z * n + x) as u32;
+                let n = n as u32;
+                indices.extend([i, i + n, i + 1, i + 1, i + n, i + n + 1]);
+            }
+        }
+        let model = ModelData {
+            bounds_min: positions.iter().fold(Vec3::INFINITY, |m, &p| m.min(Vec3::from(p))),
+            bounds_max: positions.iter().fold(Vec3::NEG_INFINITY, |m, &p| m.max(Vec3::from(p))),
+            positions,
+            indices,
+            sphere: (Vec3::ZERO, 0.0),
+        }
+        .with_bounding_sphere();
+        assert_eq!(model.triangle_count(), 3200);
+
+        let preview = model.simplified(0.25);
+        assert!(preview.triangle_count() <= 800, "{}", preview.triangle_count());
+        // Not collapsed further than needed
+        assert!(preview.triangle_count() >= 400, "{}", preview.triangle_count());
+        assert!(preview.indices.iter().all(|&i| (i as usize) < preview.vertex_count()));
+        assert!(preview.bounds_min.cmpge(model.bounds_min - 1e-5).all());
+        assert!(preview.bounds_max.cmple(model.bounds_max + 1e-5).all());
+
+        assert_eq!(model.simplified(1.0).triangle_count(), 3200);
+    }
+
+    #[test]
+    fn out_of_range_gltf_index_is_rejected() {
+        // One triangle whose last index points past the three vertices
+        let mut bin = Vec::new();
+        for v in [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]] {
+            bin.extend(v.iter().flat_map(|c| c.to_le_bytes()));
+        }
+        bin.extend([0u16, 1, 7].iter().flat_map(|i| i.to_le_bytes()));
+        bin.extend([0, 0]);
+
+        let stem = format!("scene_editor_bad_index_{}", std::process::id());
+        let dir = std::env::temp_dir();
+        let gltf = format!(
+            r#"{{
+                "asset": {{ "version": "2.0" }},
+                "buffers": [{{ "uri": "{stem}.bin", "byteLength": 44 }}],
+                "bufferViews": [
+                    {{ "buffer": 0, "byteOffset": 0, "byteLength": 36 }},
+                    {{ "buffer": 0, "byteOffset": 36, "byteLength": 6 }}
+                ],
+                "accessors": [
+                    {{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0] }},
+                    {{ "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }}
+                ],
+                "meshes": [{{ "name": "tri", "primitives": [{{ "attributes": {{ "POSITION": 0 }}, "indices": 1 }}] }}],
+                "nodes": [{{ "mesh": 0 }}],
+                "scenes": [{{ "nodes": [0] }}],
+                "scene": 0
+            }}"#
+        );
+        std::fs::write(dir.join(format!("{}.bin", stem)), &bin).unwrap();
+        let path = dir.join(format!("{}.gltf", stem));
+        std::fs::write(&path, gltf).unwrap();
+        let result = load_model(&path, &LoadProgress::default());
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(dir.join(format!("{}.bin", stem)));
+
+        let err = result.err().unwrap();
+        assert!(err.contains("vertex 7"), "{}", err);
+    }
+
+    #[test]
+    fn unsupported_extension_is_rejected() {
+        let err = load_model(Path::new("mesh.fbx"), &LoadProgress::default()).err().unwrap();
+        assert!(err.contains(".fbx"), "{}", err);
+    }
+
+    #[test]
+    fn canceled_load_stops() {
+        let progress = LoadProgress::default();
+        progress.cancel();
+        let path = std::env::temp_dir().join(format!("scene_editor_cancel_{}.gltf", std::process::id()));
+        crate::scene_data::SceneConfig::example().export_gltf(&path, crate::gltf_io::UpAxis::Y).unwrap();
+        let result = load_model(&path, &progress);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(result.err().as_deref(), Some(CANCELED));
+    }
+}
@@ -1,8 +1,10 @@
+use glam::{EulerRot, Mat3, Mat4, Quat, Vec3};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SceneConfig {
     #[serde(default)]
     pub model: ModelConfig,
@@ -10,9 +12,14 @@ pub struct SceneConfig {
     pub camera: CameraConfig,
     #[serde(default)]
     pub objects: Vec<SceneObject>,
+    #[serde(default)]
+    pub render: RenderSettings,
+    /// Light sources; scenes without the key get a single directional light
+    #[serde(default = "default_lights")]
+    pub lights: Vec<Light>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModelConfig {
     #[serde(rename = "targetLength", default = "default_target_length")]
     pub target_length: f32,
@@ -24,7 +31,7 @@ pub struct ModelConfig {
     pub position_offset: [f32; 3],
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Orientation {
     #[serde(default)]
     pub euler: [f32; 3],
@@ -34,7 +41,7 @@ pub struct Orientation {
     pub order: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Scale {
     #[serde(default = "default_scale")]
     pub uniform: f32,
@@ -42,13 +49,96 @@ pub struct Scale {
     pub xyz: [f32; 3],
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct CameraConfig {
     #[serde(default)]
     pub cockpit: CockpitCamera,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Most lights the renderer accumulates; extra lights are ignored
+pub const MAX_LIGHTS: usize = 8;
+
+/// Kind of light source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LightKind {
+    /// Parallel rays along `direction`, like the sun
+    Directional,
+    /// Shines in all directions from `position`
+    Point,
+    /// Cone from `position` along `direction`
+    Spot,
+}
+
+impl LightKind {
+    pub const ALL: [LightKind; 3] = [LightKind::Directional, LightKind::Point, LightKind::Spot];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LightKind::Directional => "Directional",
+            LightKind::Point => "Point",
+            LightKind::Spot => "Spot",
+        }
+    }
+
+    /// Whether `position` matters for this kind
+    pub fn has_position(self) -> bool {
+        self != LightKind::Directional
+    }
+
+    /// Whether `direction` matters for this kind
+    pub fn has_direction(self) -> bool {
+        self != LightKind::Point
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Light {
+    #[serde(rename = "type")]
+    pub kind: LightKind,
+    /// World position of point and spot lights
+    #[serde(default)]
+    pub position: [f32; 3],
+    /// Direction the light travels in, for directional and spot lights
+    #[serde(default = "default_light_direction")]
+    pub direction: [f32; 3],
+    /// sRGB, like every color in the config (see `srgb_to_linear`)
+    #[serde(default = "default_light_color")]
+    pub color: [f32; 3],
+    #[serde(default = "default_light_intensity")]
+    pub intensity: f32,
+    /// Spot lights: half-angle in degrees inside which the light is at full strength
+    #[serde(rename = "innerConeAngle", default = "default_inner_cone")]
+    pub inner_cone: f32,
+    /// Spot lights: half-angle in degrees at which the light has faded out
+    #[serde(rename = "outerConeAngle", default = "default_outer_cone")]
+    pub outer_cone: f32,
+}
+
+impl Light {
+    /// White light of `kind` with default direction, intensity and cone
+    pub fn new(kind: LightKind) -> Self {
+        Self {
+            kind,
+            position: [0.0, 3.0, 0.0],
+            direction: default_light_direction(),
+            color: default_light_color(),
+            intensity: default_light_intensity(),
+            inner_cone: default_inner_cone(),
+            outer_cone: default_outer_cone(),
+        }
+    }
+}
+
+/// How the scene is presented in the editor viewport
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RenderSettings {
+    /// Viewport background (sRGB, alpha)
+    #[serde(rename = "clearColor", default = "default_clear_color")]
+    pub clear_color: [f32; 4],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CockpitCamera {
     #[serde(default)]
     pub position: Position,
@@ -56,9 +146,13 @@ pub struct CockpitCamera {
     pub rotation: Rotation,
     #[serde(default = "default_fov")]
     pub fov: f32,
+    #[serde(rename = "nearPlane", default = "default_near_plane")]
+    pub near_plane: f32,
+    #[serde(rename = "farPlane", default = "default_far_plane")]
+    pub far_plane: f32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Position {
     #[serde(default)]
     pub xyz: [f32; 3],
@@ -66,7 +160,7 @@ pub struct Position {
     pub description: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Rotation {
     #[serde(default)]
     pub quaternion: [f32; 4],
@@ -76,15 +170,51 @@ pub struct Rotation {
     pub euler_unit: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SceneObject {
     pub name: String,
     pub transform: Transform,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Base color (sRGB, alpha), converted to linear and multiplied into the shaded result
+    #[serde(default = "default_color")]
+    pub color: [f32; 4],
+    /// 0 is fully transparent, 1 is opaque
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    /// Name of the parent object; the transform is relative to the parent's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// glTF model drawn at this object's transform
+    #[serde(rename = "modelPath", default, skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<String>,
+    /// Free-form labels for organizing and filtering objects
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Repeat the object, e.g. fence posts or columns; a single copy when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instances: Option<InstanceSpec>,
+}
+
+/// Copies of an object laid out in even steps
+///
+/// Copy `i` (counting the object itself as copy 0) uses the object's transform
+/// with `i × offset` added to its position and `i × rotation_euler` added to its
+/// euler angles.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InstanceSpec {
+    /// Total number of copies, including the object itself
+    #[serde(default = "default_instance_count")]
+    pub count: u32,
+    /// Position step between consecutive copies
+    #[serde(default)]
+    pub offset: [f32; 3],
+    /// Rotation step between consecutive copies, `[yaw, pitch, roll]` in degrees
+    #[serde(rename = "rotationEuler", default)]
+    pub rotation_euler: [f32; 3],
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Transform {
     #[serde(default)]
     pub position: [f32; 3],
@@ -92,6 +222,12 @@ pub struct Transform {
     pub rotation_euler: [f32; 3],  // yaw, pitch, roll in degrees
     #[serde(default = "default_scale_xyz")]
     pub scale: [f32; 3],
+    /// Point in the object's local space that rotation and scale happen around
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub pivot: [f32; 3],
+    /// Order the euler rotations are applied in, by axis letter (see `EULER_ORDERS`)
+    #[serde(default = "default_order", skip_serializing_if = "is_default_order")]
+    pub order: String,
 }
 
 // Default value functions
@@ -119,10 +255,66 @@ fn default_fov() -> f32 {
     75.0
 }
 
+fn default_near_plane() -> f32 {
+    0.1
+}
+
+fn default_far_plane() -> f32 {
+    1000.0
+}
+
 fn default_enabled() -> bool {
     true
 }
 
+fn default_color() -> [f32; 4] {
+    [1.0, 1.0, 1.0, 1.0]
+}
+
+fn is_default_order(order: &str) -> bool {
+    order == "XYZ"
+}
+
+fn is_zero(values: &[f32; 3]) -> bool {
+    values.iter().all(|&v| v == 0.0)
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+fn default_instance_count() -> u32 {
+    1
+}
+
+fn default_clear_color() -> [f32; 4] {
+    [0.1, 0.1, 0.15, 1.0]
+}
+
+fn default_lights() -> Vec<Light> {
+    vec![Light::new(LightKind::Directional)]
+}
+
+fn default_light_direction() -> [f32; 3] {
+    [-0.4, -1.0, -0.3]
+}
+
+fn default_light_color() -> [f32; 3] {
+    [1.0, 1.0, 1.0]
+}
+
+fn default_light_intensity() -> f32 {
+    1.0
+}
+
+fn default_inner_cone() -> f32 {
+    20.0
+}
+
+fn default_outer_cone() -> f32 {
+    30.0
+}
+
 impl Default for ModelConfig {
     fn default() -> Self {
         Self {
@@ -153,10 +345,10 @@ impl Default for Scale {
     }
 }
 
-impl Default for CameraConfig {
+impl Default for RenderSettings {
     fn default() -> Self {
         Self {
-            cockpit: CockpitCamera::default(),
+            clear_color: default_clear_color(),
         }
     }
 }
@@ -167,6 +359,8 @@ impl Default for CockpitCamera {
             position: Position::default(),
             rotation: Rotation::default(),
             fov: default_fov(),
+            near_plane: default_near_plane(),
+            far_plane: default_far_plane(),
         }
     }
 }
@@ -180,6 +374,18 @@ impl Default for Position {
     }
 }
 
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            position: [0.0, 0.0, 0.0],
+            rotation_euler: [0.0, 0.0, 0.0],
+            scale: default_scale_xyz(),
+            pivot: [0.0, 0.0, 0.0],
+            order: default_order(),
+        }
+    }
+}
+
 impl Default for Rotation {
     fn default() -> Self {
         Self {
@@ -190,21 +396,389 @@ impl Default for Rotation {
     }
 }
 
+/// Which edge of the selection's extent `SceneConfig::align` lines objects up on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignMode {
+    Min,
+    Center,
+    Max,
+}
+
+/// How `SceneConfig::scatter` lays out copies of a template object
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatterSettings {
+    /// Number of copies to add
+    pub count: usize,
+    /// Corners of the X/Z rectangle the copies land in, relative to the template's parent
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+    /// Copies are scaled by a random factor in `1 ± scale_variance`
+    pub scale_variance: f32,
+    /// Same seed, same layout
+    pub seed: u64,
+}
+
+impl Default for ScatterSettings {
+    fn default() -> Self {
+        Self {
+            count: 10,
+            min: [-10.0, -10.0],
+            max: [10.0, 10.0],
+            scale_variance: 0.0,
+            seed: 1,
+        }
+    }
+}
+
+/// Small seeded generator (SplitMix64), enough for reproducible layouts
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `min..max`
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        // Top 24 bits give every representable step of an f32 in [0, 1)
+        let unit = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        min + (max - min) * unit
+    }
+}
+
+/// Angle units accepted in `orientation.unit` and `rotation.eulerUnit`
+pub const ANGLE_UNITS: [&str; 2] = ["degrees", "radians"];
+
+/// Convert euler angles expressed in `unit` to radians; anything but "radians" is read as degrees
+pub fn euler_to_radians(euler: [f32; 3], unit: &str) -> [f32; 3] {
+    if unit == "radians" {
+        euler
+    } else {
+        euler.map(f32::to_radians)
+    }
+}
+
+/// Convert euler angles from one unit to another
+pub fn convert_euler(euler: [f32; 3], from: &str, to: &str) -> [f32; 3] {
+    let radians = euler_to_radians(euler, from);
+    if to == "radians" {
+        radians
+    } else {
+        radians.map(f32::to_degrees)
+    }
+}
+
+/// Convert an sRGB color to linear RGB for shading; a fourth (alpha) channel is left alone
+///
+/// Colors in a scene config (object base colors, light colors, the viewport
+/// background) are authored in sRGB, the way color pickers and hex codes show
+/// them. Lighting and blending need linear values, so colors pass through here on
+/// their way to the renderer, and an sRGB render target encodes the result back.
+pub fn srgb_to_linear<const N: usize>(color: [f32; N]) -> [f32; N] {
+    let mut linear = color;
+    for c in linear.iter_mut().take(3) {
+        *c = if *c <= 0.04045 { *c / 12.92 } else { ((*c + 0.055) / 1.055).powf(2.4) };
+    }
+    linear
+}
+
+/// Inverse of `srgb_to_linear`
+pub fn linear_to_srgb<const N: usize>(color: [f32; N]) -> [f32; N] {
+    let mut srgb = color;
+    for c in srgb.iter_mut().take(3) {
+        *c = if *c <= 0.0031308 { *c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+    }
+    srgb
+}
+
+/// Sibling file used for crash-safety autosaves, e.g. `scene.json` -> `scene.autosave.json`
+pub fn autosave_path<P: AsRef<Path>>(config_path: P) -> PathBuf {
+    let path = config_path.as_ref();
+    let stem = path.file_stem().unwrap_or(path.as_os_str()).to_string_lossy();
+    path.with_file_name(format!("{}.autosave.json", stem))
+}
+
+/// Replace NaN and infinite entries with `default`, returning whether any were found
+fn replace_non_finite(values: &mut [f32], default: f32) -> bool {
+    let mut replaced = false;
+    for value in values.iter_mut().filter(|v| !v.is_finite()) {
+        *value = default;
+        replaced = true;
+    }
+    replaced
+}
+
+/// Check that clip planes describe a usable depth range
+pub fn validate_clip_planes(near: f32, far: f32) -> Result<(), String> {
+    if !near.is_finite() || near <= 0.0 {
+        Err(format!("near plane {} must be greater than zero", near))
+    } else if !far.is_finite() || far <= near {
+        Err(format!("far plane {} must be beyond near plane {}", far, near))
+    } else {
+        Ok(())
+    }
+}
+
+/// Euler rotation orders accepted in `orientation.order`
+pub const EULER_ORDERS: [&str; 6] = ["XYZ", "XZY", "YXZ", "YZX", "ZXY", "ZYX"];
+
+/// Build a rotation from euler angles (radians, indexed X/Y/Z) applied in `order`
+///
+/// "XYZ" rotates about X first, then Y, then Z. Unknown orders fall back to "XYZ".
+pub fn euler_to_quat(euler: [f32; 3], order: &str) -> Quat {
+    let order = if EULER_ORDERS.contains(&order) { order } else { "XYZ" };
+    order.chars().fold(Quat::IDENTITY, |acc, axis| {
+        let step = match axis {
+            'X' => Quat::from_rotation_x(euler[0]),
+            'Y' => Quat::from_rotation_y(euler[1]),
+            _ => Quat::from_rotation_z(euler[2]),
+        };
+        step * acc
+    })
+}
+
+/// Recover euler angles (radians, indexed X/Y/Z) for `order` from a rotation
+///
+/// Inverse of [`euler_to_quat`]. Unknown orders fall back to "XYZ".
+pub fn quat_to_euler(rotation: Quat, order: &str) -> [f32; 3] {
+    // Applying axes A, B, C in turn is glam's intrinsic C-B-A rotation
+    let (glam_order, axes) = match order {
+        "XZY" => (EulerRot::YZX, [1, 2, 0]),
+        "YXZ" => (EulerRot::ZXY, [2, 0, 1]),
+        "YZX" => (EulerRot::XZY, [0, 2, 1]),
+        "ZXY" => (EulerRot::YXZ, [1, 0, 2]),
+        "ZYX" => (EulerRot::XYZ, [0, 1, 2]),
+        _ => (EulerRot::ZYX, [2, 1, 0]),
+    };
+    let (a, b, c) = rotation.to_euler(glam_order);
+    let mut euler = [0.0; 3];
+    euler[axes[0]] = a;
+    euler[axes[1]] = b;
+    euler[axes[2]] = c;
+    euler
+}
+
+impl Orientation {
+    /// Switch units, converting the stored angles so the orientation is unchanged
+    pub fn set_unit(&mut self, unit: &str) {
+        self.euler = convert_euler(self.euler, &self.unit, unit);
+        self.unit = unit.to_string();
+    }
+}
+
+impl Rotation {
+    /// Switch units, converting the stored angles so the rotation is unchanged
+    pub fn set_euler_unit(&mut self, unit: &str) {
+        self.euler = convert_euler(self.euler, &self.euler_unit, unit);
+        self.euler_unit = unit.to_string();
+    }
+
+    /// Stored quaternion ([x, y, z, w]) as a unit rotation; degenerate values read as identity
+    pub fn quat(&self) -> Quat {
+        let q = Quat::from_array(self.quaternion);
+        if q.length_squared() > 1e-12 && q.is_finite() {
+            q.normalize()
+        } else {
+            Quat::IDENTITY
+        }
+    }
+
+    /// Recompute the quaternion from the euler angles
+    pub fn quat_from_euler(&mut self) {
+        let euler = euler_to_radians(self.euler, &self.euler_unit);
+        self.quaternion = euler_to_quat(euler, "XYZ").to_array();
+    }
+
+    /// Recompute the euler angles from the quaternion
+    pub fn euler_from_quat(&mut self) {
+        let euler = quat_to_euler(self.quat(), "XYZ");
+        self.euler = convert_euler(euler, "radians", &self.euler_unit);
+    }
+
+    /// Whether the stored quaternion is unit length, allowing for rounding in hand-written files
+    pub fn is_unit(&self) -> bool {
+        (Quat::from_array(self.quaternion).length() - 1.0).abs() < 1e-3
+    }
+
+    /// Replace a non-unit or degenerate quaternion with its normalized form
+    pub fn normalize(&mut self) {
+        self.quaternion = self.quat().to_array();
+    }
+}
+
+impl SceneObject {
+    /// Number of copies drawn: the instance count, or 1 without instancing
+    pub fn instance_count(&self) -> u32 {
+        self.instances.as_ref().map_or(1, |spec| spec.count.max(1))
+    }
+}
+
+impl Transform {
+    /// Rotation from `rotation_euler`: yaw about Y, pitch about X, roll about Z,
+    /// applied in `order` by axis (the default "XYZ" applies pitch, then yaw, then roll)
+    pub fn rotation(&self) -> Quat {
+        let [yaw, pitch, roll] = self.rotation_euler.map(f32::to_radians);
+        euler_to_quat([pitch, yaw, roll], &self.order)
+    }
+
+    /// Local matrix: move the pivot to the origin, scale, rotate, move it back, then translate
+    pub fn to_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(
+            Vec3::from(self.scale),
+            self.rotation(),
+            Vec3::from(self.position) + self.pivot_offset(),
+        )
+    }
+
+    /// Matrix for transforming normals: the inverse-transpose of the local matrix's 3x3 part
+    ///
+    /// Unlike the model matrix itself, this keeps normals perpendicular to their
+    /// surfaces under non-uniform scale. Results need renormalizing.
+    pub fn normal_matrix(&self) -> Mat3 {
+        Mat3::from_mat4(self.to_matrix()).inverse().transpose()
+    }
+
+    /// Whether non-uniform scale is combined with a rotation, the case where
+    /// normals transformed by the model matrix come out visibly wrong
+    pub fn skews_normals(&self) -> bool {
+        let scale = Vec3::from(self.scale).abs();
+        let non_uniform = scale.max_element() - scale.min_element() > 1e-4 * scale.max_element();
+        non_uniform && !self.rotation().abs_diff_eq(Quat::IDENTITY, 1e-5)
+    }
+
+    /// Extra translation that makes scale and rotation happen around `pivot`
+    ///
+    /// Adding this to `position` gives the plain scale/rotation/translation
+    /// equivalent, e.g. for formats without pivots.
+    pub fn pivot_offset(&self) -> Vec3 {
+        let pivot = Vec3::from(self.pivot);
+        pivot - self.rotation() * (Vec3::from(self.scale) * pivot)
+    }
+
+    /// Move the pivot to a new local point, shifting `position` so the geometry stays put
+    pub fn set_pivot_in_place(&mut self, pivot: Vec3) {
+        let offset = self.pivot_offset();
+        self.pivot = pivot.to_array();
+        self.position = (Vec3::from(self.position) + offset - self.pivot_offset()).to_array();
+    }
+
+    /// Set `rotation_euler` from a rotation; inverse of [`Transform::rotation`]
+    pub fn set_rotation(&mut self, rotation: Quat) {
+        let [pitch, yaw, roll] = quat_to_euler(rotation, &self.order).map(f32::to_degrees);
+        self.rotation_euler = [yaw, pitch, roll];
+    }
+
+    /// Reflect across the plane where `axis` (0 = X, 1 = Y, 2 = Z) is zero
+    ///
+    /// The result is the reflection applied after this transform: the position
+    /// component flips, rotations about the other two axes reverse and the scale
+    /// on `axis` goes negative, so the geometry itself is flipped. Mirroring twice
+    /// restores the original.
+    pub fn mirror(&mut self, axis: usize) {
+        // rotation_euler is [yaw (Y), pitch (X), roll (Z)]
+        let kept = [1, 0, 2][axis];
+        for (i, angle) in self.rotation_euler.iter_mut().enumerate() {
+            if i != kept {
+                *angle = -*angle;
+            }
+        }
+        // Keep the pivot where it is in local space
+        self.position[axis] = -self.position[axis] - 2.0 * self.pivot[axis];
+        self.scale[axis] = -self.scale[axis];
+    }
+}
+
+impl CockpitCamera {
+    /// Look direction: the rotation applied to +Z, matching the engine's cockpit camera
+    pub fn forward(&self) -> Vec3 {
+        self.rotation.quat() * Vec3::Z
+    }
+
+    /// Right-handed view matrix looking along `forward` from `position`
+    pub fn view_matrix(&self) -> Mat4 {
+        let forward = self.forward();
+        // Straight up or down has no defined roll against +Y; fall back to +Z
+        let up = if forward.cross(Vec3::Y).length_squared() < 1e-6 { Vec3::Z } else { Vec3::Y };
+        Mat4::look_to_rh(Vec3::from(self.position.xyz), forward, up)
+    }
+
+    /// Right-handed perspective with a 0..1 depth range; `fov` is the vertical angle
+    pub fn projection_matrix(&self, aspect: f32) -> Mat4 {
+        Mat4::perspective_rh(self.fov.to_radians(), aspect, self.near_plane, self.far_plane)
+    }
+}
+
+impl ModelConfig {
+    /// Combined uniform and per-axis scale
+    pub fn combined_scale(&self) -> Vec3 {
+        Vec3::from(self.scale.xyz) * self.scale.uniform
+    }
+
+    /// Set the uniform scale so the longest axis of a mesh of `size` equals `target_length`
+    ///
+    /// The per-axis scale is kept. Returns false, changing nothing, for an empty or degenerate mesh.
+    pub fn normalize_to_target(&mut self, size: Vec3) -> bool {
+        let longest = (size * Vec3::from(self.scale.xyz)).abs().max_element();
+        let uniform = self.target_length / longest;
+        if longest <= 0.0 || !uniform.is_finite() {
+            return false;
+        }
+        self.scale.uniform = uniform;
+        true
+    }
+
+    /// Base transform applied to the loaded mesh: scale, then orientation, then offset
+    pub fn base_matrix(&self) -> Mat4 {
+        let euler = euler_to_radians(self.orientation.euler, &self.orientation.unit);
+        Mat4::from_scale_rotation_translation(
+            self.combined_scale(),
+            euler_to_quat(euler, &self.orientation.order),
+            Vec3::from(self.position_offset),
+        )
+    }
+}
+
 impl SceneConfig {
     /// Load scene configuration from JSON file
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
         let json_str = fs::read_to_string(path)?;
-        let config: SceneConfig = serde_json::from_str(&json_str)?;
-        Ok(config)
+        Self::from_json(&json_str)
+    }
+
+    /// Parse scene configuration from JSON text
+    ///
+    /// The camera quaternion is kept as written, so `validate` can point out a
+    /// non-unit one; `Rotation::quat` normalizes it wherever it is used.
+    pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_json::from_str(json_str)?)
     }
 
     /// Save scene configuration to JSON file
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
-        let json_str = serde_json::to_string_pretty(self)?;
-        fs::write(path, json_str)?;
+        fs::write(path, self.to_json()?)?;
         Ok(())
     }
 
+    /// Scene configuration as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Empty scene with default model and camera settings
+    pub fn blank() -> Self {
+        Self {
+            model: ModelConfig::default(),
+            camera: CameraConfig::default(),
+            objects: Vec::new(),
+            render: RenderSettings::default(),
+            lights: default_lights(),
+        }
+    }
+
     /// Create example scene with model and camera configuration
     pub fn example() -> Self {
         Self {
@@ -233,6 +807,8 @@ impl SceneConfig {
                         euler_unit: "degrees".to_string(),
                     },
                     fov: 75.0,
+                    near_plane: 0.1,
+                    far_plane: 1000.0,
                 },
             },
             objects: vec![
@@ -242,8 +818,16 @@ impl SceneConfig {
                         position: [0.0, 0.0, 0.0],
                         rotation_euler: [0.0, 180.0, 0.0],
                         scale: [1.0, 1.0, 1.0],
+                        pivot: [0.0, 0.0, 0.0],
+                        order: "XYZ".to_string(),
                     },
                     enabled: true,
+                    color: [1.0, 1.0, 1.0, 1.0],
+                    opacity: 1.0,
+                    parent: None,
+                    model_path: None,
+                    tags: Vec::new(),
+                    instances: None,
                 },
                 SceneObject {
                     name: "Road_Surface".to_string(),
@@ -251,10 +835,915 @@ impl SceneConfig {
                         position: [0.0, -1.0, 0.0],
                         rotation_euler: [0.0, 0.0, 0.0],
                         scale: [10.0, 1.0, 10.0],
+                        pivot: [0.0, 0.0, 0.0],
+                        order: "XYZ".to_string(),
                     },
                     enabled: true,
+                    color: [0.3, 0.3, 0.32, 1.0],
+                    opacity: 1.0,
+                    parent: None,
+                    model_path: None,
+                    tags: Vec::new(),
+                    instances: None,
                 },
             ],
+            render: RenderSettings::default(),
+            lights: default_lights(),
+        }
+    }
+
+    /// Fix values that would break rendering, returning a description of each change
+    ///
+    /// Non-finite numbers are replaced with their defaults and the FOV is clamped
+    /// to the 30-120° the editor allows. Odd but usable values (such as a zero
+    /// scale) are left for [`SceneConfig::validate`] to warn about.
+    pub fn sanitize(&mut self) -> Vec<String> {
+        let mut fixes = Vec::new();
+        let mut fix = |values: &mut [f32], default: f32, what: &str| {
+            if replace_non_finite(values, default) {
+                fixes.push(format!("{}: replaced non-finite values with {}", what, default));
+            }
+        };
+
+        let model = &mut self.model;
+        fix(std::slice::from_mut(&mut model.target_length), default_target_length(), "Model target length");
+        fix(&mut model.orientation.euler, 0.0, "Model orientation");
+        fix(std::slice::from_mut(&mut model.scale.uniform), default_scale(), "Model uniform scale");
+        fix(&mut model.scale.xyz, 1.0, "Model scale");
+        fix(&mut model.position_offset, 0.0, "Model position offset");
+
+        let cockpit = &mut self.camera.cockpit;
+        fix(&mut cockpit.position.xyz, 0.0, "Cockpit position");
+        fix(&mut cockpit.rotation.euler, 0.0, "Cockpit rotation");
+        fix(std::slice::from_mut(&mut cockpit.fov), default_fov(), "Cockpit FOV");
+        fix(std::slice::from_mut(&mut cockpit.near_plane), default_near_plane(), "Cockpit near plane");
+        fix(std::slice::from_mut(&mut cockpit.far_plane), default_far_plane(), "Cockpit far plane");
+
+        for obj in &mut self.objects {
+            let t = &mut obj.transform;
+            fix(&mut t.position, 0.0, &format!("{} position", obj.name));
+            fix(&mut t.rotation_euler, 0.0, &format!("{} rotation", obj.name));
+            fix(&mut t.scale, 1.0, &format!("{} scale", obj.name));
+            fix(&mut t.pivot, 0.0, &format!("{} pivot", obj.name));
+            fix(&mut obj.color, 1.0, &format!("{} color", obj.name));
+            fix(std::slice::from_mut(&mut obj.opacity), default_opacity(), &format!("{} opacity", obj.name));
+        }
+
+        for (i, light) in self.lights.iter_mut().enumerate() {
+            let what = format!("Light {}", i + 1);
+            fix(&mut light.position, 0.0, &format!("{} position", what));
+            fix(&mut light.direction, 0.0, &format!("{} direction", what));
+            fix(&mut light.color, 1.0, &format!("{} color", what));
+            fix(std::slice::from_mut(&mut light.intensity), default_light_intensity(), &format!("{} intensity", what));
+            fix(std::slice::from_mut(&mut light.inner_cone), default_inner_cone(), &format!("{} inner cone", what));
+            fix(std::slice::from_mut(&mut light.outer_cone), default_outer_cone(), &format!("{} outer cone", what));
+        }
+
+        let clear_color = &mut self.render.clear_color;
+        if clear_color.iter().any(|v| !v.is_finite()) {
+            *clear_color = default_clear_color();
+            fixes.push("Viewport background: replaced non-finite color with the default".to_string());
+        }
+
+        let cockpit = &mut self.camera.cockpit;
+        let fov = cockpit.fov.clamp(30.0, 120.0);
+        if fov != cockpit.fov {
+            fixes.push(format!("Cockpit FOV: clamped {} to {}", cockpit.fov, fov));
+            cockpit.fov = fov;
+        }
+
+        fixes
+    }
+
+    /// Check for values that load fine but render strangely; returns human-readable warnings
+    pub fn validate(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let non_finite = |values: &[f32]| values.iter().any(|v| !v.is_finite());
+
+        if non_finite(&[self.model.target_length, self.model.scale.uniform])
+            || non_finite(&self.model.orientation.euler)
+            || non_finite(&self.model.scale.xyz)
+            || non_finite(&self.model.position_offset)
+        {
+            warnings.push("Model configuration contains non-finite values".to_string());
+        }
+
+        let cockpit = &self.camera.cockpit;
+        if non_finite(&cockpit.position.xyz) || non_finite(&cockpit.rotation.euler) || non_finite(&cockpit.rotation.quaternion) {
+            warnings.push("Cockpit camera contains non-finite values".to_string());
+        } else if !cockpit.rotation.is_unit() {
+            warnings.push("Cockpit camera quaternion is not unit length; it is normalized when used".to_string());
+        }
+        if !(1.0..=179.0).contains(&cockpit.fov) {
+            warnings.push(format!("Cockpit camera FOV {} is outside 1-179°", cockpit.fov));
+        }
+        if let Err(e) = validate_clip_planes(cockpit.near_plane, cockpit.far_plane) {
+            warnings.push(format!("Cockpit camera: {}", e));
+        }
+
+        for (idx, obj) in self.objects.iter().enumerate() {
+            if self.objects[..idx].iter().any(|other| other.name == obj.name) {
+                warnings.push(format!("Duplicate object name: {}", obj.name));
+            }
+
+            let t = &obj.transform;
+            if non_finite(&t.position) || non_finite(&t.rotation_euler) || non_finite(&t.scale) {
+                warnings.push(format!("{}: transform contains non-finite values", obj.name));
+            }
+            // Negative components are fine: that is how mirrored objects are stored
+            if t.scale.contains(&0.0) {
+                warnings.push(format!("{}: scale {:?} has a zero component", obj.name, t.scale));
+            }
+            if obj.instances.as_ref().is_some_and(|spec| spec.count == 0) {
+                warnings.push(format!("{}: instance count 0 is drawn as a single copy", obj.name));
+            }
+            if !(0.0..=1.0).contains(&obj.opacity) {
+                warnings.push(format!("{}: opacity {} is outside 0-1", obj.name, obj.opacity));
+            }
+
+            if let Some(parent) = &obj.parent {
+                if self.find_object(parent).is_none() {
+                    warnings.push(format!("{}: parent {} does not exist", obj.name, parent));
+                }
+            }
+        }
+
+        if self.lights.len() > MAX_LIGHTS {
+            warnings.push(format!("{} lights; only the first {} are rendered", self.lights.len(), MAX_LIGHTS));
+        }
+        for (i, light) in self.lights.iter().enumerate() {
+            let name = format!("Light {} ({})", i + 1, light.kind.label());
+            if light.kind.has_direction() && Vec3::from(light.direction).length_squared() == 0.0 {
+                warnings.push(format!("{}: direction is zero", name));
+            }
+            if light.intensity < 0.0 {
+                warnings.push(format!("{}: intensity {} is negative", name, light.intensity));
+            }
+            if light.kind == LightKind::Spot && !(0.0 <= light.inner_cone && light.inner_cone <= light.outer_cone && light.outer_cone < 90.0) {
+                warnings.push(format!(
+                    "{}: cone angles {}°/{}° need 0 ≤ inner ≤ outer < 90",
+                    name, light.inner_cone, light.outer_cone
+                ));
+            }
+        }
+
+        warnings
+    }
+
+    /// Enable or disable the given objects, returning how many actually changed
+    pub fn set_enabled(&mut self, indices: &[usize], enabled: bool) -> usize {
+        let mut changed = 0;
+        for &idx in indices {
+            if let Some(obj) = self.objects.get_mut(idx).filter(|obj| obj.enabled != enabled) {
+                obj.enabled = enabled;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Add a tag to an object, returning false if it was empty or already present
+    pub fn add_tag(&mut self, idx: usize, tag: &str) -> bool {
+        let tag = tag.trim();
+        let tags = &mut self.objects[idx].tags;
+        if tag.is_empty() || tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            return false;
+        }
+        tags.push(tag.to_string());
+        true
+    }
+
+    /// Index of the object with the given name
+    pub fn find_object(&self, name: &str) -> Option<usize> {
+        self.objects.iter().position(|obj| obj.name == name)
+    }
+
+    /// `base` if no object uses it, otherwise `base` with a numeric suffix that is free
+    ///
+    /// An existing `_N` suffix on `base` is incremented rather than stacked, so
+    /// "Object_3" becomes "Object_4", not "Object_3_2".
+    pub fn unique_name(&self, base: &str) -> String {
+        if self.find_object(base).is_none() {
+            return base.to_string();
+        }
+        let (stem, mut suffix) = match base.rsplit_once('_') {
+            Some((stem, digits)) if !stem.is_empty() => match digits.parse::<u32>() {
+                Ok(n) => (stem, n),
+                Err(_) => (base, 1),
+            },
+            _ => (base, 1),
+        };
+        loop {
+            suffix += 1;
+            let name = format!("{}_{}", stem, suffix);
+            if self.find_object(&name).is_none() {
+                return name;
+            }
+        }
+    }
+
+    /// Rename an object, disambiguating the name and updating its children's parent links
+    ///
+    /// Returns the name actually given, which differs from `name` if it was taken.
+    pub fn rename_object(&mut self, idx: usize, name: &str) -> String {
+        let old = self.objects[idx].name.clone();
+        if name == old {
+            return old;
+        }
+        let name = self.unique_name(name);
+        for obj in &mut self.objects {
+            if obj.parent.as_deref() == Some(old.as_str()) {
+                obj.parent = Some(name.clone());
+            }
+        }
+        self.objects[idx].name = name.clone();
+        name
+    }
+
+    /// Index of an object's parent, if it names an existing object
+    pub fn parent_index(&self, idx: usize) -> Option<usize> {
+        self.objects[idx]
+            .parent
+            .as_deref()
+            .and_then(|name| self.find_object(name))
+    }
+
+    /// Whether `parent` can become the parent of `child` without creating a cycle
+    pub fn can_parent(&self, child: usize, parent: usize) -> bool {
+        let mut current = Some(parent);
+        let mut steps = 0;
+        while let Some(idx) = current {
+            if idx == child {
+                return false;
+            }
+            // Bail out on cycles already present in a hand-edited file
+            steps += 1;
+            if steps > self.objects.len() {
+                return false;
+            }
+            current = self.parent_index(idx);
+        }
+        true
+    }
+
+    /// Set or clear an object's parent, refusing assignments that would form a cycle
+    pub fn set_parent(&mut self, child: usize, parent: Option<usize>) -> Result<(), String> {
+        match parent {
+            Some(parent) if !self.can_parent(child, parent) => Err(format!(
+                "{} cannot be parented to {}: this would create a cycle",
+                self.objects[child].name, self.objects[parent].name
+            )),
+            Some(parent) => {
+                self.objects[child].parent = Some(self.objects[parent].name.clone());
+                Ok(())
+            }
+            None => {
+                self.objects[child].parent = None;
+                Ok(())
+            }
+        }
+    }
+
+    /// Remove an object, reattaching its children to its own parent
+    pub fn remove_object(&mut self, idx: usize) -> SceneObject {
+        let removed = self.objects.remove(idx);
+        for obj in &mut self.objects {
+            if obj.parent.as_deref() == Some(removed.name.as_str()) {
+                obj.parent = removed.parent.clone();
+            }
+        }
+        removed
+    }
+
+    /// World matrix of an object: its local matrix under each ancestor's in turn
+    pub fn world_matrix(&self, idx: usize) -> Mat4 {
+        let mut matrix = self.objects[idx].transform.to_matrix();
+        let mut current = self.parent_index(idx);
+        let mut steps = 0;
+        // A hand-edited cycle would loop forever; stop after visiting every object once
+        while let Some(parent) = current.filter(|_| steps < self.objects.len()) {
+            matrix = self.objects[parent].transform.to_matrix() * matrix;
+            current = self.parent_index(parent);
+            steps += 1;
+        }
+        matrix
+    }
+
+    /// World-space position of an object's origin
+    pub fn world_position(&self, idx: usize) -> Vec3 {
+        self.world_matrix(idx).transform_point3(Vec3::ZERO)
+    }
+
+    /// Place an object's origin at a world position, solving for it in the parent's space
+    pub fn set_world_position(&mut self, idx: usize, world: Vec3) {
+        let parent_world = self.parent_index(idx).map_or(Mat4::IDENTITY, |parent| self.world_matrix(parent));
+        let transform = &mut self.objects[idx].transform;
+        let local = parent_world.inverse().transform_point3(world) - transform.pivot_offset();
+        transform.position = local.to_array();
+    }
+
+    /// Move an object so the masked axes of its world position match `target`'s
+    ///
+    /// Position and rotation are solved in the object's parent space, so snapping
+    /// works across different parents. With `rotation`, the object also takes on
+    /// the target's world orientation.
+    pub fn snap_to(&mut self, idx: usize, target: usize, axes: [bool; 3], rotation: bool) {
+        let mut world = self.world_position(idx).to_array();
+        let target_world = self.world_position(target).to_array();
+        for axis in 0..3 {
+            if axes[axis] {
+                world[axis] = target_world[axis];
+            }
+        }
+
+        // Rotate first: the pivot offset depends on the rotation, and the position solve uses it
+        if rotation {
+            let world_rotation = |matrix: Mat4| matrix.to_scale_rotation_translation().1;
+            let parent_rotation = self.parent_index(idx).map_or(Quat::IDENTITY, |parent| world_rotation(self.world_matrix(parent)));
+            let local = parent_rotation.inverse() * world_rotation(self.world_matrix(target));
+            self.objects[idx].transform.set_rotation(local);
+        }
+        self.set_world_position(idx, Vec3::from(world));
+    }
+
+    /// Line up the enabled objects among `indices` on one world axis
+    ///
+    /// Returns false when fewer than two enabled objects were given.
+    pub fn align(&mut self, indices: &[usize], axis: usize, mode: AlignMode) -> bool {
+        let targets: Vec<usize> = indices.iter().copied().filter(|&idx| self.objects[idx].enabled).collect();
+        if targets.len() < 2 {
+            return false;
+        }
+
+        let coords: Vec<f32> = targets.iter().map(|&idx| self.world_position(idx)[axis]).collect();
+        let min = coords.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = coords.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let value = match mode {
+            AlignMode::Min => min,
+            AlignMode::Center => (min + max) * 0.5,
+            AlignMode::Max => max,
+        };
+
+        for idx in targets {
+            let mut world = self.world_position(idx);
+            world[axis] = value;
+            self.set_world_position(idx, world);
+        }
+        true
+    }
+
+    /// Space the enabled objects among `indices` evenly along one world axis
+    ///
+    /// The outermost objects stay put. Returns false when fewer than three enabled
+    /// objects were given, since there is nothing in between to move.
+    pub fn distribute(&mut self, indices: &[usize], axis: usize) -> bool {
+        let mut targets: Vec<(usize, f32)> = indices
+            .iter()
+            .copied()
+            .filter(|&idx| self.objects[idx].enabled)
+            .map(|idx| (idx, self.world_position(idx)[axis]))
+            .collect();
+        if targets.len() < 3 {
+            return false;
+        }
+
+        targets.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let first = targets[0].1;
+        let step = (targets[targets.len() - 1].1 - first) / (targets.len() - 1) as f32;
+        for (i, &(idx, _)) in targets.iter().enumerate() {
+            let mut world = self.world_position(idx);
+            world[axis] = first + step * i as f32;
+            self.set_world_position(idx, world);
+        }
+        true
+    }
+
+    /// Add randomly placed copies of `template`, returning their indices
+    ///
+    /// Each copy keeps the template's height, parent and other settings, lands at
+    /// a random X/Z inside the settings' rectangle with a random yaw, and is scaled
+    /// by the variance factor. Names follow the template's with numeric suffixes.
+    pub fn scatter(&mut self, template: usize, settings: &ScatterSettings) -> Vec<usize> {
+        let mut rng = SplitMix64(settings.seed);
+        let source = self.objects[template].clone();
+        let variance = settings.scale_variance.abs();
+
+        let mut added = Vec::with_capacity(settings.count);
+        for _ in 0..settings.count {
+            let mut copy = source.clone();
+            copy.name = self.unique_name(&source.name);
+            let t = &mut copy.transform;
+            t.position[0] = rng.range(settings.min[0], settings.max[0]);
+            t.position[2] = rng.range(settings.min[1], settings.max[1]);
+            t.rotation_euler[0] = rng.range(0.0, 360.0);
+            let factor = rng.range(1.0 - variance, 1.0 + variance);
+            t.scale = t.scale.map(|s| s * factor);
+
+            added.push(self.objects.len());
+            self.objects.push(copy);
+        }
+        added
+    }
+
+    /// Objects in tree display order as (index, depth) pairs
+    ///
+    /// Objects whose parent is missing, or which sit on a cycle, are listed as roots.
+    pub fn hierarchy_order(&self) -> Vec<(usize, usize)> {
+        let count = self.objects.len();
+        let parents: Vec<Option<usize>> = (0..count).map(|idx| self.parent_index(idx)).collect();
+        let mut visited = vec![false; count];
+        let mut order = Vec::with_capacity(count);
+
+        let mut roots: Vec<usize> = (0..count).filter(|&idx| parents[idx].is_none()).collect();
+        let mut next_root = 0;
+        loop {
+            while next_root < roots.len() {
+                let mut stack = vec![(roots[next_root], 0)];
+                next_root += 1;
+                while let Some((idx, depth)) = stack.pop() {
+                    if visited[idx] {
+                        continue;
+                    }
+                    visited[idx] = true;
+                    order.push((idx, depth));
+                    // Push in reverse so children display in file order
+                    for child in (0..count).rev() {
+                        if parents[child] == Some(idx) && !visited[child] {
+                            stack.push((child, depth + 1));
+                        }
+                    }
+                }
+            }
+            match (0..count).find(|&idx| !visited[idx]) {
+                Some(idx) => roots.push(idx),
+                None => break,
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named_objects(names: &[&str]) -> SceneConfig {
+        let mut config = SceneConfig::example();
+        let template = config.objects[0].clone();
+        config.objects = names
+            .iter()
+            .map(|name| SceneObject { name: name.to_string(), ..template.clone() })
+            .collect();
+        config
+    }
+
+    fn assert_vec_eq(a: Vec3, b: Vec3) {
+        assert!(a.abs_diff_eq(b, 1e-5), "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn euler_order_controls_composition() {
+        let euler = [90f32.to_radians(), 0.0, 90f32.to_radians()];
+
+        // X first leaves +X alone, then Z turns it to +Y
+        assert_vec_eq(euler_to_quat(euler, "XYZ") * Vec3::X, Vec3::Y);
+        // Z first turns +X to +Y, then X turns it to +Z
+        assert_vec_eq(euler_to_quat(euler, "ZYX") * Vec3::X, Vec3::Z);
+    }
+
+    #[test]
+    fn unknown_euler_order_falls_back_to_xyz() {
+        let euler = [0.3, -1.2, 0.7];
+        assert!(euler_to_quat(euler, "bogus").abs_diff_eq(euler_to_quat(euler, "XYZ"), 1e-6));
+    }
+
+    #[test]
+    fn quat_to_euler_inverts_every_order() {
+        let euler = [0.4, -0.3, 1.1];
+        for order in EULER_ORDERS {
+            let q = euler_to_quat(euler, order);
+            let back = quat_to_euler(q, order);
+            assert!(euler_to_quat(back, order).abs_diff_eq(q, 1e-5), "order {}", order);
+            for (a, b) in back.iter().zip(euler) {
+                assert!((a - b).abs() < 1e-4, "order {}: {:?}", order, back);
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_sync_and_degenerate_quaternion() {
+        let mut rotation = Rotation {
+            quaternion: [0.0, 0.0, 0.0, 0.0],
+            euler: [0.0, 90.0, 0.0],
+            euler_unit: "degrees".to_string(),
+        };
+        assert!(!rotation.is_unit());
+        assert_eq!(rotation.quat(), Quat::IDENTITY);
+        rotation.normalize();
+        assert_eq!(rotation.quaternion, [0.0, 0.0, 0.0, 1.0]);
+        assert!(rotation.is_unit());
+
+        // Scaled quaternions read as the same rotation and normalize to unit length
+        rotation.quaternion = [0.0, 2.0, 0.0, 2.0];
+        assert!(!rotation.is_unit());
+        assert!(rotation.quat().abs_diff_eq(Quat::from_rotation_y(90f32.to_radians()), 1e-6));
+        rotation.normalize();
+        assert!(rotation.is_unit());
+        assert!((rotation.quaternion[1] - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+
+        rotation.quat_from_euler();
+        assert!(rotation.quat().abs_diff_eq(Quat::from_rotation_y(90f32.to_radians()), 1e-6));
+
+        rotation.euler = [0.0; 3];
+        rotation.euler_from_quat();
+        assert!((rotation.euler[1] - 90.0).abs() < 1e-3, "{:?}", rotation.euler);
+    }
+
+    #[test]
+    fn validate_flags_suspicious_values() {
+        assert!(SceneConfig::example().validate().is_empty());
+
+        let mut config = SceneConfig::example();
+        config.camera.cockpit.fov = 180.0;
+        config.objects[1].name = config.objects[0].name.clone();
+        config.objects[0].transform.position[1] = f32::NAN;
+        config.objects[1].transform.scale = [1.0, 0.0, 1.0];
+        config.camera.cockpit.rotation.quaternion = [0.0, 0.0, 0.0, 0.0];
+
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 5, "{:?}", warnings);
+        assert!(warnings.iter().any(|w| w.contains("FOV")));
+        assert!(warnings.iter().any(|w| w.contains("Duplicate")));
+        assert!(warnings.iter().any(|w| w.contains("non-finite")));
+        assert!(warnings.iter().any(|w| w.contains("zero component")));
+        assert!(warnings.iter().any(|w| w.contains("unit length")));
+    }
+
+    #[test]
+    fn cockpit_view_matrix_looks_along_rotation() {
+        let mut cockpit = SceneConfig::example().camera.cockpit;
+        cockpit.rotation.quaternion = Quat::from_euler(EulerRot::YXZ, 0.7, -0.2, 0.0).to_array();
+
+        let expected = Quat::from_array(cockpit.rotation.quaternion) * Vec3::Z;
+        assert_vec_eq(cockpit.forward(), expected);
+
+        // A view matrix maps the look direction onto -Z
+        let view = cockpit.view_matrix();
+        assert_vec_eq(view.transform_vector3(expected), Vec3::NEG_Z);
+        assert_vec_eq(view.transform_point3(Vec3::from(cockpit.position.xyz)), Vec3::ZERO);
+    }
+
+    #[test]
+    fn transform_matrix_for_known_transform() {
+        let transform = Transform {
+            position: [1.0, 2.0, 3.0],
+            rotation_euler: [90.0, 0.0, 0.0],
+            scale: [2.0, 1.0, 1.0],
+            ..Transform::default()
+        };
+
+        // Yaw 90° turns +X to -Z; scale X by 2, then translate
+        let expected = Mat4::from_cols_array(&[
+            0.0, 0.0, -2.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            1.0, 0.0, 0.0, 0.0, //
+            1.0, 2.0, 3.0, 1.0,
+        ]);
+        assert!(transform.to_matrix().abs_diff_eq(expected, 1e-6), "{:?}", transform.to_matrix());
+    }
+
+    #[test]
+    fn world_matrix_composes_parents() {
+        let mut config = SceneConfig::example();
+        config.objects[0].transform = Transform {
+            position: [0.0, 1.0, 0.0],
+            rotation_euler: [90.0, 0.0, 0.0],
+            scale: [1.0, 1.0, 1.0],
+            ..Transform::default()
+        };
+        config.objects[1].transform = Transform {
+            position: [1.0, 0.0, 0.0],
+            ..Transform::default()
+        };
+        config.objects[1].parent = Some(config.objects[0].name.clone());
+
+        let origin = config.world_matrix(1).transform_point3(Vec3::ZERO);
+        assert_vec_eq(origin, Vec3::new(0.0, 1.0, -1.0));
+    }
+
+    #[test]
+    fn euler_unit_conversion_round_trips() {
+        let degrees = [90.0, -45.0, 180.0];
+        let radians = convert_euler(degrees, "degrees", "radians");
+        assert!((radians[0] - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((radians[1] + std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+        assert!((radians[2] - std::f32::consts::PI).abs() < 1e-6);
+
+        let back = convert_euler(radians, "radians", "degrees");
+        for (a, b) in back.iter().zip(degrees) {
+            assert!((a - b).abs() < 1e-4);
+        }
+
+        assert_eq!(euler_to_radians(radians, "radians"), radians);
+        assert_eq!(convert_euler(degrees, "degrees", "degrees"), degrees);
+    }
+
+    #[test]
+    fn switching_orientation_unit_keeps_base_matrix() {
+        let mut model = ModelConfig::default();
+        model.orientation.euler = [10.0, 180.0, -30.0];
+        let before = model.base_matrix();
+
+        model.orientation.set_unit("radians");
+        assert_eq!(model.orientation.unit, "radians");
+        assert!(model.base_matrix().abs_diff_eq(before, 1e-5));
+    }
+
+    #[test]
+    fn model_base_matrix_applies_scale_rotation_offset() {
+        let mut model = ModelConfig::default();
+        model.orientation.euler = [0.0, 90.0, 0.0];
+        model.scale.uniform = 2.0;
+        model.position_offset = [0.0, 1.0, 0.0];
+
+        let p = model.base_matrix().transform_point3(Vec3::X);
+        assert_vec_eq(p, Vec3::new(0.0, 1.0, -2.0));
+    }
+
+    #[test]
+    fn unique_name_after_deletion() {
+        let mut config = named_objects(&["Object_1", "Object_2", "Object_3"]);
+        config.remove_object(1);
+
+        // Numbering by count would hand out "Object_3" again
+        let name = config.unique_name(&format!("Object_{}", config.objects.len() + 1));
+        assert_eq!(name, "Object_4");
+        assert!(config.find_object(&name).is_none());
+
+        assert_eq!(config.unique_name("Object_2"), "Object_2");
+        assert_eq!(config.unique_name("Object_1"), "Object_2");
+    }
+
+    #[test]
+    fn rename_disambiguates_and_keeps_children() {
+        let mut config = named_objects(&["Car", "Wheel", "Door"]);
+        config.objects[1].parent = Some("Car".to_string());
+
+        assert_eq!(config.rename_object(0, "Door"), "Door_2");
+        assert_eq!(config.objects[1].parent.as_deref(), Some("Door_2"));
+        assert_eq!(config.rename_object(2, "Door"), "Door");
+    }
+
+    #[test]
+    fn rotation_about_pivot() {
+        let transform = Transform {
+            rotation_euler: [90.0, 0.0, 0.0],
+            pivot: [1.0, 0.0, 0.0],
+            ..Transform::default()
+        };
+        let matrix = transform.to_matrix();
+
+        // The pivot stays put while the origin swings around it: yaw 90° turns -X to +Z
+        assert_vec_eq(matrix.transform_point3(Vec3::X), Vec3::X);
+        assert_vec_eq(matrix.transform_point3(Vec3::ZERO), Vec3::new(1.0, 0.0, 1.0));
+
+        // Scale also happens around the pivot, then position moves everything
+        let scaled = Transform {
+            position: [0.0, 5.0, 0.0],
+            scale: [2.0, 2.0, 2.0],
+            pivot: [1.0, 0.0, 0.0],
+            ..Transform::default()
+        };
+        assert_vec_eq(scaled.to_matrix().transform_point3(Vec3::X), Vec3::new(1.0, 5.0, 0.0));
+        assert_vec_eq(scaled.to_matrix().transform_point3(Vec3::ZERO), Vec3::new(-1.0, 5.0, 0.0));
+    }
+
+    #[test]
+    fn transform_respects_euler_order() {
+        // Yaw 90° and roll 90°: [yaw, pitch, roll]
+        let mut transform = Transform {
+            rotation_euler: [90.0, 0.0, 90.0],
+            ..Transform::default()
+        };
+
+        // XYZ: yaw turns +X to -Z, then roll leaves -Z alone and turns +Y to -X
+        let expected_xyz = Mat4::from_cols_array(&[
+            0.0, 0.0, -1.0, 0.0, //
+            -1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ]);
+        assert!(transform.to_matrix().abs_diff_eq(expected_xyz, 1e-6), "{:?}", transform.to_matrix());
+
+        // ZYX: roll turns +X to +Y first, which yaw then leaves alone
+        transform.order = "ZYX".to_string();
+        let expected_zyx = Mat4::from_cols_array(&[
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ]);
+        assert!(transform.to_matrix().abs_diff_eq(expected_zyx, 1e-6), "{:?}", transform.to_matrix());
+
+        // Setting a rotation solves for angles in the transform's own order
+        let rotation = transform.rotation();
+        transform.rotation_euler = [0.0; 3];
+        transform.set_rotation(rotation);
+        assert!(transform.rotation().abs_diff_eq(rotation, 1e-5));
+    }
+
+    #[test]
+    fn normal_matrix_keeps_normals_perpendicular() {
+        let transform = Transform {
+            rotation_euler: [30.0, 45.0, 0.0],
+            scale: [3.0, 1.0, 0.5],
+            ..Transform::default()
+        };
+        assert!(transform.skews_normals());
+
+        // A slanted surface: the tangent and normal are perpendicular before and after
+        let tangent = Vec3::new(1.0, -1.0, 0.0);
+        let normal = Vec3::new(1.0, 1.0, 0.0);
+        let model = Mat3::from_mat4(transform.to_matrix());
+        assert!((model * tangent).dot(transform.normal_matrix() * normal).abs() < 1e-5);
+        // Transforming the normal by the model matrix itself would not be
+        assert!((model * tangent).dot(model * normal).abs() > 0.1);
+
+        let uniform = Transform {
+            scale: [2.0, 2.0, 2.0],
+            ..transform.clone()
+        };
+        assert!(!uniform.skews_normals());
+        let unrotated = Transform {
+            rotation_euler: [0.0; 3],
+            ..transform
+        };
+        assert!(!unrotated.skews_normals());
+    }
+
+    #[test]
+    fn scatter_is_reproducible_and_in_bounds() {
+        let mut config = named_objects(&["Rock"]);
+        config.objects[0].transform.position = [0.0, 2.0, 0.0];
+        let settings = ScatterSettings {
+            count: 50,
+            min: [-5.0, 10.0],
+            max: [5.0, 20.0],
+            scale_variance: 0.25,
+            seed: 42,
+        };
+
+        let mut again = config.clone();
+        let added = config.scatter(0, &settings);
+        assert_eq!(added, (1..51).collect::<Vec<_>>());
+        assert_eq!(config.objects[1].name, "Rock_2");
+        assert!(config.validate().is_empty(), "{:?}", config.validate());
+
+        for obj in &config.objects[1..] {
+            let t = &obj.transform;
+            assert!((-5.0..5.0).contains(&t.position[0]) && (10.0..20.0).contains(&t.position[2]), "{:?}", t.position);
+            assert_eq!(t.position[1], 2.0);
+            assert!((0.0..360.0).contains(&t.rotation_euler[0]));
+            assert!((0.75..=1.25).contains(&t.scale[0]) && t.scale[0] == t.scale[2]);
+        }
+
+        // The same seed gives the same layout
+        again.scatter(0, &settings);
+        for (a, b) in config.objects.iter().zip(&again.objects) {
+            assert_eq!(a.transform.position, b.transform.position);
+            assert_eq!(a.transform.rotation_euler, b.transform.rotation_euler);
+        }
+    }
+
+    #[test]
+    fn schema_uses_serde_names() {
+        let schema = serde_json::to_value(schemars::schema_for!(SceneConfig)).unwrap();
+        let definitions = &schema["definitions"];
+        assert!(definitions["ModelConfig"]["properties"]["targetLength"].is_object());
+        assert!(definitions["Transform"]["properties"]["rotationEuler"].is_object());
+        assert!(definitions["SceneObject"]["properties"]["modelPath"].is_object());
+        // Fields with defaults are optional; the object name is not
+        assert_eq!(definitions["SceneObject"]["required"], serde_json::json!(["name", "transform"]));
+    }
+
+    #[test]
+    fn lights_default_and_validate() {
+        // Scenes written before lights existed get one directional light
+        let config = SceneConfig::from_json(r#"{ "objects": [] }"#).unwrap();
+        assert_eq!(config.lights.len(), 1);
+        assert_eq!(config.lights[0].kind, LightKind::Directional);
+
+        let json = r#"{ "lights": [{ "type": "spot", "position": [0, 5, 0], "innerConeAngle": 40, "outerConeAngle": 30 }] }"#;
+        let config = SceneConfig::from_json(json).unwrap();
+        assert_eq!(config.lights[0].direction, default_light_direction());
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("cone angles"));
+    }
+
+    #[test]
+    fn non_unit_quaternion_survives_loading() {
+        let json = r#"{ "camera": { "cockpit": { "rotation": { "quaternion": [0, 2, 0, 2] } } } }"#;
+        let config = SceneConfig::from_json(json).unwrap();
+        let rotation = &config.camera.cockpit.rotation;
+        assert_eq!(rotation.quaternion, [0.0, 2.0, 0.0, 2.0]);
+        assert!(rotation.quat().abs_diff_eq(Quat::from_rotation_y(90f32.to_radians()), 1e-6));
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("unit length"));
+    }
+
+    #[test]
+    fn snap_matches_world_rotation_across_parents() {
+        let mut config = SceneConfig::example();
+        let mut child = config.objects[1].clone();
+        child.name = "Child".to_string();
+        child.parent = Some(config.objects[0].name.clone());
+        child.transform.rotation_euler = [10.0, 0.0, 0.0];
+        config.objects.push(child);
+        config.objects[0].transform.rotation_euler = [90.0, 0.0, 0.0];
+        config.objects[0].transform.position = [3.0, 0.0, 1.0];
+        config.objects[1].transform.rotation_euler = [30.0, 15.0, 0.0];
+        config.objects[1].transform.position = [-2.0, 1.0, 4.0];
+
+        let child = config.objects.len() - 1;
+        config.snap_to(child, 1, [true; 3], true);
+
+        let rotation = |matrix: Mat4| matrix.to_scale_rotation_translation().1;
+        let (got, want) = (rotation(config.world_matrix(child)), rotation(config.world_matrix(1)));
+        assert!(got.dot(want).abs() > 1.0 - 1e-5, "{:?} != {:?}", got, want);
+        assert!(config.world_position(child).abs_diff_eq(config.world_position(1), 1e-4));
+    }
+
+    #[test]
+    fn default_pivot_and_order_are_not_saved() {
+        let mut config = SceneConfig::example();
+        let json = serde_json::to_value(&config.objects).unwrap().to_string();
+        assert!(!json.contains("\"order\"") && !json.contains("\"pivot\""), "{}", json);
+
+        config.objects[0].transform.order = "ZXY".to_string();
+        let reloaded = SceneConfig::from_json(&config.to_json().unwrap()).unwrap();
+        assert_eq!(reloaded.objects[0].transform.order, "ZXY");
+        assert_eq!(reloaded.objects[1].transform.order, "XYZ");
+    }
+
+    #[test]
+    fn pivot_moves_in_place() {
+        let mut transform = Transform {
+            position: [1.0, 2.0, -3.0],
+            rotation_euler: [30.0, -20.0, 75.0],
+            scale: [1.0, 2.0, 0.5],
+            pivot: [0.5, -1.0, 2.0],
+            order: "ZXY".to_string(),
+        };
+        let before = transform.to_matrix();
+
+        let center = Vec3::new(-4.0, 0.25, 3.0);
+        transform.set_pivot_in_place(center);
+        assert_eq!(transform.pivot, center.to_array());
+        assert!(transform.to_matrix().abs_diff_eq(before, 1e-5));
+        // The pivot lands at position + pivot, and turning about it leaves it there
+        let pivot_world = before.transform_point3(center);
+        assert!(pivot_world.abs_diff_eq(Vec3::from(transform.position) + center, 1e-5));
+        transform.rotation_euler[0] += 90.0;
+        assert!(transform.to_matrix().transform_point3(center).abs_diff_eq(pivot_world, 1e-5));
+    }
+
+    #[test]
+    fn mirror_reflects_matrix() {
+        let original = Transform {
+            position: [1.0, 2.0, -3.0],
+            rotation_euler: [30.0, -20.0, 75.0],
+            scale: [1.0, 2.0, 0.5],
+            pivot: [0.5, -1.0, 2.0],
+            order: "ZXY".to_string(),
+        };
+
+        for axis in 0..3 {
+            let mut flip = Vec3::ONE;
+            flip[axis] = -1.0;
+            let expected = Mat4::from_scale(flip) * original.to_matrix();
+
+            let mut mirrored = original.clone();
+            mirrored.mirror(axis);
+            assert!(mirrored.to_matrix().abs_diff_eq(expected, 1e-5), "axis {}: {:?}", axis, mirrored.to_matrix());
+            assert!(mirrored.to_matrix().determinant() < 0.0);
+
+            mirrored.mirror(axis);
+            assert!(mirrored.to_matrix().abs_diff_eq(original.to_matrix(), 1e-5));
+        }
+    }
+
+    #[test]
+    fn srgb_linear_round_trip() {
+        // sRGB mid gray is about 21% linear; alpha passes through untouched
+        let linear = srgb_to_linear([0.5, 0.0, 1.0, 0.5]);
+        assert!((linear[0] - 0.214_04).abs() < 1e-4, "{:?}", linear);
+        assert_eq!(&linear[1..], &[0.0, 1.0, 0.5]);
+
+        for value in [0.0, 0.02, 0.04045, 0.1, 0.5, 0.9, 1.0] {
+            let back = linear_to_srgb(srgb_to_linear([value; 3]));
+            assert!(back.iter().all(|v| (v - value).abs() < 1e-5), "{} -> {:?}", value, back);
         }
     }
 }
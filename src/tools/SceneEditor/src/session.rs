@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Session file, in the directory the editor is started from
+pub const SESSION_FILE: &str = "session.json";
+
+/// Working state restored when the editor is reopened on the same config
+///
+/// Kept apart from the scene config so it never ends up in version-controlled
+/// scene files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorSession {
+    #[serde(rename = "configPath")]
+    pub config_path: String,
+    /// Name of the selected object; names survive reordering where indices do not
+    #[serde(rename = "selectedObject", default, skip_serializing_if = "Option::is_none")]
+    pub selected_object: Option<String>,
+    /// Inner window size in points
+    #[serde(rename = "windowSize", default, skip_serializing_if = "Option::is_none")]
+    pub window_size: Option<[f32; 2]>,
+    /// Color theme; unlike the rest, applied whichever config is opened
+    #[serde(default)]
+    pub theme: Theme,
+}
+
+/// Editor color theme
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Follow the operating system setting
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Light, Theme::Dark, Theme::System];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::System => "System",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+        }
+    }
+
+    pub fn preference(self) -> egui::ThemePreference {
+        match self {
+            Theme::System => egui::ThemePreference::System,
+            Theme::Light => egui::ThemePreference::Light,
+            Theme::Dark => egui::ThemePreference::Dark,
+        }
+    }
+}
+
+impl EditorSession {
+    /// The saved session, if there is one and it belongs to `config_path`
+    pub fn load_for(config_path: &str) -> Option<Self> {
+        Self::load().filter(|session| same_file(&session.config_path, config_path))
+    }
+
+    /// Theme from the last run, whichever config it was on
+    pub fn load_theme() -> Theme {
+        Self::load().map(|session| session.theme).unwrap_or_default()
+    }
+
+    fn load() -> Option<Self> {
+        let json_str = fs::read_to_string(SESSION_FILE).ok()?;
+        serde_json::from_str(&json_str).ok()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let json_str = serde_json::to_string_pretty(self)?;
+        fs::write(SESSION_FILE, json_str)?;
+        Ok(())
+    }
+}
+
+/// Whether two paths name the same file, falling back to comparing them as written
+fn same_file(a: &str, b: &str) -> bool {
+    let canonical = |path: &str| fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+    canonical(a) == canonical(b) || Path::new(a) == Path::new(b)
+}
@@ -1,56 +1,378 @@
-use crate::scene_data::{SceneConfig, SceneObject};
+use crate::diff::Change;
+use crate::expr::eval_expression;
+use crate::frustum::Frustum;
+use crate::gltf_io::UpAxis;
+use crate::log::{Log, LogLevel};
+use crate::model::{relative_model_path, resolve_model_path, ModelCache};
+use crate::watcher::ConfigWatcher;
+use crate::session::{EditorSession, Theme};
+use crate::scene_data::{autosave_path, AlignMode, validate_clip_planes, InstanceSpec, Light, LightKind, MAX_LIGHTS, ScatterSettings, SceneConfig, SceneObject, Transform, linear_to_srgb, srgb_to_linear, ANGLE_UNITS, EULER_ORDERS};
 use egui::{Context, SidePanel, CentralPanel, ScrollArea, DragValue, Ui};
+use glam::{Mat4, Vec3};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+/// File operation waiting for the user to confirm a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathAction {
+    ExportGltf,
+    ImportGltf,
+    ExportCsv,
+    ImportCsv,
+}
+
+impl PathAction {
+    fn title(self) -> &'static str {
+        match self {
+            PathAction::ExportGltf => "Export glTF",
+            PathAction::ImportGltf => "Import glTF Nodes",
+            PathAction::ExportCsv => "Export CSV",
+            PathAction::ImportCsv => "Import CSV Transforms",
+        }
+    }
+}
+
+/// Which objects the list shows by their enabled flag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnabledFilter {
+    #[default]
+    All,
+    Enabled,
+    Disabled,
+}
+
+impl EnabledFilter {
+    const ALL: [EnabledFilter; 3] = [EnabledFilter::All, EnabledFilter::Enabled, EnabledFilter::Disabled];
+
+    fn label(self) -> &'static str {
+        match self {
+            EnabledFilter::All => "All",
+            EnabledFilter::Enabled => "Enabled",
+            EnabledFilter::Disabled => "Disabled",
+        }
+    }
+
+    fn matches(self, enabled: bool) -> bool {
+        match self {
+            EnabledFilter::All => true,
+            EnabledFilter::Enabled => enabled,
+            EnabledFilter::Disabled => !enabled,
+        }
+    }
+}
+
+/// Action that would discard unsaved changes
+#[derive(Debug, Clone)]
+pub enum PendingAction {
+    Reload,
+    Exit,
+    /// Replace the config with the scene file at this path
+    Open(String),
+    /// Replace the config with one pasted from the clipboard
+    Replace(Box<SceneConfig>),
+    /// Start a new, not yet saved scene: the example one if true, otherwise blank
+    NewScene(bool),
+}
 
 pub struct EditorState {
     pub config: SceneConfig,
     pub selected_object: Option<usize>,
     pub config_path: String,
     pub unsaved_changes: bool,
-    pub status_message: Option<String>,
+    /// Latest message, shown in the menu bar; every message also goes to `log`
+    pub status_message: Option<(LogLevel, String)>,
+    /// All messages since startup, shown in the log console
+    pub log: Log,
+    pub show_log: bool,
+    /// Edit the camera rotation as a quaternion instead of euler angles
+    pub rotation_as_quaternion: bool,
+    /// Warnings from the last config validation, shown until dismissed
+    pub validation_warnings: Vec<String>,
+    /// Parsed models referenced by objects, loaded in the background
+    pub models: ModelCache,
+    /// Draw models simplified to `simplify_ratio` of their triangles
+    pub simplify_preview: bool,
+    pub simplify_ratio: f32,
+    /// Show the frame-time overlay in the central panel
+    pub show_frame_stats: bool,
+    /// Time spent building the previous frame
+    pub last_frame_time: Duration,
+    /// Object awaiting delete confirmation
+    pub pending_delete: Option<usize>,
+    /// Action waiting on the Save / Discard / Cancel prompt
+    pub pending_action: Option<PendingAction>,
+    /// Set once the user has agreed to close the window
+    allow_close: bool,
+    /// How often unsaved changes are written to the autosave file; `None` disables it
+    pub autosave_interval: Option<Duration>,
+    last_autosave: Instant,
+    /// Autosave found at startup that the user may recover
+    pub recovery_path: Option<PathBuf>,
+    /// Case-insensitive name filter for the object list
+    pub object_filter: String,
+    /// Enabled-state filter for the object list
+    pub enabled_filter: EnabledFilter,
+    /// File operation and the path being edited for it
+    pub path_prompt: Option<(PathAction, String)>,
+    /// JSON being pasted while the Paste Config dialog is open
+    pub paste_buffer: Option<String>,
+    /// Up axis convention assumed for glTF import/export
+    pub gltf_up_axis: UpAxis,
+    /// Object the "Snap to" action copies from
+    pub snap_target: Option<usize>,
+    /// World axes (X/Y/Z) the snap copies
+    pub snap_axes: [bool; 3],
+    /// Also copy the target's rotation when snapping
+    pub snap_rotation: bool,
+    /// All selected objects; Ctrl/Cmd-click in the list adds or removes entries
+    pub multi_selection: BTreeSet<usize>,
+    /// Per-axis edit locks for position, rotation and scale; editor-only, not saved
+    pub axis_locks: [[bool; 3]; 3],
+    /// Show transform fields as text boxes for typing exact values
+    pub exact_entry: bool,
+    /// Decimal places used by Copy Position
+    pub copy_precision: usize,
+    /// "x, y, z" text typed or pasted for Paste Position
+    position_paste: String,
+    /// Differences from the file on disk, while the Changes window is open
+    pub changes: Option<Vec<Change>>,
+    /// Settings of the scatter tool
+    pub scatter: ScatterSettings,
+    /// Color theme, saved with the session
+    pub theme: Theme,
+    /// Per-axis position step of the nudge buttons and Alt+arrow keys
+    pub nudge_step: [f32; 3],
+    /// Rotation step of the nudge buttons, in degrees
+    pub nudge_angle: f32,
+    /// Tag being typed in the transform editor
+    tag_input: String,
+    /// Scroll the object list to the selection on the next frame (after keyboard navigation)
+    scroll_to_selected: bool,
+    /// Name being typed for the selected object, applied when the field loses focus
+    name_edit: Option<String>,
+    /// Watcher on the config file, started on the first frame
+    watcher: Option<ConfigWatcher>,
+    watcher_started: bool,
+    /// Modification time of the config file when it was last loaded or saved
+    known_modified: Option<SystemTime>,
+    /// The config file was changed by another program
+    pub file_changed_on_disk: bool,
+    /// Reload external changes automatically when there are no unsaved edits
+    pub auto_reload: bool,
 }
 
 impl EditorState {
     pub fn new(config: SceneConfig, config_path: String) -> Self {
+        let known_modified = modified_time(&config_path);
         Self {
+            validation_warnings: Vec::new(),
             config,
             selected_object: None,
             config_path,
             unsaved_changes: false,
             status_message: None,
+            log: Log::default(),
+            show_log: false,
+            rotation_as_quaternion: false,
+            models: ModelCache::default(),
+            simplify_preview: false,
+            simplify_ratio: 0.25,
+            show_frame_stats: false,
+            last_frame_time: Duration::ZERO,
+            pending_delete: None,
+            pending_action: None,
+            allow_close: false,
+            autosave_interval: Some(Duration::from_secs(60)),
+            last_autosave: Instant::now(),
+            recovery_path: None,
+            object_filter: String::new(),
+            enabled_filter: EnabledFilter::default(),
+            path_prompt: None,
+            paste_buffer: None,
+            gltf_up_axis: UpAxis::default(),
+            snap_target: None,
+            snap_axes: [true; 3],
+            snap_rotation: false,
+            multi_selection: BTreeSet::new(),
+            name_edit: None,
+            tag_input: String::new(),
+            scroll_to_selected: false,
+            exact_entry: false,
+            copy_precision: 3,
+            position_paste: String::new(),
+            changes: None,
+            scatter: ScatterSettings::default(),
+            theme: Theme::default(),
+            nudge_step: [0.1, 0.1, 0.1],
+            nudge_angle: 15.0,
+            axis_locks: [[false; 3]; 3],
+            watcher: None,
+            watcher_started: false,
+            known_modified,
+            file_changed_on_disk: false,
+            auto_reload: false,
         }
     }
 
     pub fn render(&mut self, ctx: &Context) {
+        let frame_start = Instant::now();
+        self.poll_pending_load();
+
+        if ctx.input(|i| i.viewport().close_requested()) && !self.allow_close {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.request_action(ctx, PendingAction::Exit);
+        }
+        if let Some(path) = self.selected_model_path() {
+            self.models.request(path, ctx);
+        }
+        // Alt+arrows first: plain-arrow matching ignores extra Alt
+        self.handle_nudge_keys(ctx);
+        self.handle_list_navigation(ctx);
+
         // Top menu bar
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.label("DownPour Scene Editor");
                 ui.separator();
 
+                ui.menu_button("📁 File", |ui| {
+                    ui.menu_button("📄 New Scene", |ui| {
+                        if ui.button("Blank").clicked() {
+                            self.request_action(ctx, PendingAction::NewScene(false));
+                            ui.close_menu();
+                        }
+                        if ui.button("From Example").clicked() {
+                            self.request_action(ctx, PendingAction::NewScene(true));
+                            ui.close_menu();
+                        }
+                    });
+                    if ui.button("Show Changes…").on_hover_text("Compare the editor's config with the file on disk").clicked() {
+                        self.show_changes();
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button("Export glTF…").clicked() {
+                        self.path_prompt = Some((PathAction::ExportGltf, self.sibling_path("gltf")));
+                        ui.close_menu();
+                    }
+                    if ui.button("Import glTF Nodes…").clicked() {
+                        self.path_prompt = Some((PathAction::ImportGltf, self.sibling_path("gltf")));
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button("Export CSV…").clicked() {
+                        self.path_prompt = Some((PathAction::ExportCsv, self.sibling_path("csv")));
+                        ui.close_menu();
+                    }
+                    if ui.button("Import CSV Transforms…").clicked() {
+                        self.path_prompt = Some((PathAction::ImportCsv, self.sibling_path("csv")));
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button("Copy Config").on_hover_text("Copy the whole config to the clipboard as JSON").clicked() {
+                        self.copy_config(ctx);
+                        ui.close_menu();
+                    }
+                    if ui.button("Paste Config…").on_hover_text("Replace the config with JSON from the clipboard").clicked() {
+                        self.paste_buffer = Some(String::new());
+                        ui.close_menu();
+                    }
+                });
+
                 if ui.button("💾 Save").clicked() {
                     self.save();
                 }
 
                 if ui.button("🔄 Reload").clicked() {
-                    self.reload();
+                    self.request_action(ctx, PendingAction::Reload);
                 }
 
                 if ui.button("📋 New Object").clicked() {
                     self.add_new_object();
                 }
 
+                ui.menu_button("👁 View", |ui| {
+                    ui.checkbox(&mut self.show_log, "Log Console");
+                    ui.separator();
+                    ui.label("Theme");
+                    for theme in Theme::ALL {
+                        if ui.radio_value(&mut self.theme, theme, theme.label()).clicked() {
+                            ctx.set_theme(theme.preference());
+                            ui.close_menu();
+                        }
+                    }
+                });
+
+                ui.separator();
+
+                ui.checkbox(&mut self.show_frame_stats, "FPS")
+                    .on_hover_text("Show frame time; repaints continuously while enabled");
+
                 ui.separator();
 
                 if self.unsaved_changes {
                     ui.label("⚠ Unsaved changes");
                 }
 
-                if let Some(ref msg) = self.status_message {
-                    ui.colored_label(egui::Color32::GREEN, msg);
+                if let Some(path) = self.models.loading() {
+                    match self.models.progress() {
+                        Some(fraction) => {
+                            ui.add(egui::ProgressBar::new(fraction).desired_width(120.0).show_percentage());
+                        }
+                        None => {
+                            ui.spinner();
+                        }
+                    }
+                    ui.label(format!("Loading {}…", path.display()));
+                    if ui.small_button("✖ Cancel").on_hover_text("Stop loading this model").clicked() {
+                        self.cancel_load();
+                    }
+                }
+
+                if let Some((level, ref msg)) = self.status_message {
+                    ui.colored_label(level.color(ui.visuals()), format!("{} {}", level.icon(), msg));
                 }
             });
         });
 
+        // External change notice
+        if self.file_changed_on_disk {
+            egui::TopBottomPanel::top("file_changed_panel").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(ui.visuals().warn_fg_color, "⚠ File changed on disk");
+                    if ui.button("🔄 Reload").clicked() {
+                        self.request_action(ctx, PendingAction::Reload);
+                    }
+                    if ui.button("Ignore").clicked() {
+                        self.file_changed_on_disk = false;
+                        self.known_modified = modified_time(&self.config_path);
+                    }
+                    ui.checkbox(&mut self.auto_reload, "Auto-reload when there are no unsaved changes");
+                });
+            });
+        }
+
+        if self.show_log {
+            self.render_log_panel(ctx);
+        }
+        self.render_changes_window(ctx);
+
+        // Validation warnings
+        if !self.validation_warnings.is_empty() {
+            egui::TopBottomPanel::top("warnings_panel").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {} config warning(s)", self.validation_warnings.len()));
+                    if ui.button("Dismiss").clicked() {
+                        self.validation_warnings.clear();
+                    }
+                });
+                for warning in &self.validation_warnings {
+                    ui.label(format!("• {}", warning));
+                }
+            });
+        }
+
         // Left panel: Object list
         SidePanel::left("objects_panel")
             .default_width(300.0)
@@ -70,16 +392,59 @@ impl EditorState {
                         }
 
                         ui.label("Orientation (Euler):");
+                        let unit = self.config.model.orientation.unit.clone();
                         for (i, label) in ["X", "Y", "Z"].iter().enumerate() {
                             ui.horizontal(|ui| {
                                 ui.label(format!("{}:", label));
                                 if ui.add(DragValue::new(&mut self.config.model.orientation.euler[i])
-                                    .speed(1.0)
-                                    .suffix("°")).changed() {
+                                    .speed(angle_speed(&unit))
+                                    .suffix(angle_suffix(&unit))).changed() {
+                                    self.unsaved_changes = true;
+                                }
+                            });
+                        }
+
+                        ui.horizontal(|ui| {
+                            ui.label("Unit:");
+                            if let Some(new_unit) = unit_combo(ui, "model_angle_unit", &unit) {
+                                self.config.model.orientation.set_unit(new_unit);
+                                self.unsaved_changes = true;
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Order:");
+                            egui::ComboBox::from_id_salt("model_euler_order")
+                                .selected_text(&self.config.model.orientation.order)
+                                .show_ui(ui, |ui| {
+                                    for order in EULER_ORDERS {
+                                        if ui.selectable_value(&mut self.config.model.orientation.order, order.to_string(), order).changed() {
+                                            self.unsaved_changes = true;
+                                        }
+                                    }
+                                });
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Uniform Scale:");
+                            if ui.add(DragValue::new(&mut self.config.model.scale.uniform).speed(0.01)).changed() {
+                                self.unsaved_changes = true;
+                            }
+                        });
+
+                        ui.label("Position Offset:");
+                        for (i, label) in ["X", "Y", "Z"].iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{}:", label));
+                                if ui.add(DragValue::new(&mut self.config.model.position_offset[i])
+                                    .speed(0.01)).changed() {
                                     self.unsaved_changes = true;
                                 }
                             });
                         }
+
+                        ui.label("Base Transform:");
+                        matrix_grid(ui, "model_base_matrix", &self.config.model.base_matrix());
                     });
 
                     // Camera configuration
@@ -95,6 +460,69 @@ impl EditorState {
                             });
                         }
 
+                        ui.horizontal(|ui| {
+                            ui.label("Rotation:");
+                            ui.selectable_value(&mut self.rotation_as_quaternion, false, "Euler");
+                            ui.selectable_value(&mut self.rotation_as_quaternion, true, "Quaternion");
+                        });
+
+                        let rotation = &mut self.config.camera.cockpit.rotation;
+                        if !rotation.is_unit() {
+                            ui.horizontal(|ui| {
+                                ui.colored_label(ui.visuals().warn_fg_color, "⚠ Quaternion is not unit length")
+                                    .on_hover_text("Read as its normalized form; all zeros reads as no rotation");
+                                if ui.small_button("Normalize").clicked() {
+                                    rotation.normalize();
+                                    rotation.euler_from_quat();
+                                    self.unsaved_changes = true;
+                                }
+                            });
+                        }
+                        if self.rotation_as_quaternion {
+                            for (i, label) in ["X", "Y", "Z", "W"].iter().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("{}:", label));
+                                    if ui.add(DragValue::new(&mut rotation.quaternion[i])
+                                        .speed(0.005)
+                                        .range(-1.0..=1.0)
+                                        // Leave loaded out-of-range values alone; the non-unit warning covers them
+                                        .clamp_existing_to_range(false)
+                                        .max_decimals(4)).changed() {
+                                        rotation.euler_from_quat();
+                                        self.unsaved_changes = true;
+                                    }
+                                });
+                            }
+                        } else {
+                            let unit = rotation.euler_unit.clone();
+                            for (i, label) in ["X", "Y", "Z"].iter().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("{}:", label));
+                                    if ui.add(DragValue::new(&mut rotation.euler[i])
+                                        .speed(angle_speed(&unit))
+                                        .suffix(angle_suffix(&unit))).changed() {
+                                        rotation.quat_from_euler();
+                                        self.unsaved_changes = true;
+                                    }
+                                });
+                            }
+                        }
+
+                        ui.horizontal(|ui| {
+                            ui.label("Unit:");
+                            let unit = rotation.euler_unit.clone();
+                            if let Some(new_unit) = unit_combo(ui, "camera_angle_unit", &unit) {
+                                rotation.set_euler_unit(new_unit);
+                                self.unsaved_changes = true;
+                            }
+                        });
+
+                        let forward = self.config.camera.cockpit.forward();
+                        ui.label(format!("Looking toward: ({:.2}, {:.2}, {:.2})", forward.x, forward.y, forward.z));
+                        ui.collapsing("View Matrix", |ui| {
+                            matrix_grid(ui, "cockpit_view_matrix", &self.config.camera.cockpit.view_matrix());
+                        });
+
                         ui.label("FOV:");
                         if ui.add(DragValue::new(&mut self.config.camera.cockpit.fov)
                             .speed(1.0)
@@ -102,29 +530,161 @@ impl EditorState {
                             .suffix("°")).changed() {
                             self.unsaved_changes = true;
                         }
+
+                        let cockpit = &mut self.config.camera.cockpit;
+                        ui.horizontal(|ui| {
+                            ui.label("Near:");
+                            if ui.add(DragValue::new(&mut cockpit.near_plane)
+                                .speed(0.01)
+                                .range(0.001..=f32::MAX)).changed() {
+                                self.unsaved_changes = true;
+                            }
+                            ui.label("Far:");
+                            if ui.add(DragValue::new(&mut cockpit.far_plane)
+                                .speed(1.0)
+                                .range(0.001..=f32::MAX)).changed() {
+                                self.unsaved_changes = true;
+                            }
+                        });
+                        if let Err(e) = validate_clip_planes(cockpit.near_plane, cockpit.far_plane) {
+                            ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", e));
+                        }
                     });
 
                     ui.separator();
-                    ui.heading("Objects");
 
-                    for (idx, obj) in self.config.objects.iter().enumerate() {
-                        let is_selected = self.selected_object == Some(idx);
+                    // Viewport settings
+                    ui.collapsing("Viewport", |ui| {
+                        let clear_color = &mut self.config.render.clear_color;
+                        ui.horizontal(|ui| {
+                            ui.label("Background:");
+                            if srgba_edit(ui, clear_color) {
+                                self.unsaved_changes = true;
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            for (label, color) in [
+                                ("Dark", [0.1, 0.1, 0.15, 1.0]),
+                                ("Gray", [0.5, 0.5, 0.5, 1.0]),
+                                ("White", [1.0, 1.0, 1.0, 1.0]),
+                            ] {
+                                if ui.small_button(label).clicked() && *clear_color != color {
+                                    *clear_color = color;
+                                    self.unsaved_changes = true;
+                                }
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.simplify_preview, "Simplify preview")
+                                .on_hover_text("Draw heavy models with fewer triangles; the full mesh stays loaded");
+                            ui.add_enabled(
+                                self.simplify_preview,
+                                egui::Slider::new(&mut self.simplify_ratio, 0.05..=1.0)
+                                    .step_by(0.05)
+                                    .custom_formatter(|ratio, _| format!("{:.0}%", ratio * 100.0)),
+                            );
+                        });
+                    });
+
+                    ui.collapsing("Lights", |ui| {
+                        self.render_lights(ui);
+                    });
+
+                    ui.separator();
+                    ui.heading("Objects");
 
-                        if ui.selectable_label(is_selected, &obj.name).clicked() {
-                            self.selected_object = Some(idx);
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut self.object_filter)
+                            .hint_text("🔍 Filter by name or tag:name")
+                            .desired_width(180.0));
+                        if !self.object_filter.is_empty() && ui.small_button("✖").clicked() {
+                            self.object_filter.clear();
                         }
+                        egui::ComboBox::from_id_salt("enabled_filter")
+                            .selected_text(self.enabled_filter.label())
+                            .width(80.0)
+                            .show_ui(ui, |ui| {
+                                for filter in EnabledFilter::ALL {
+                                    ui.selectable_value(&mut self.enabled_filter, filter, filter.label());
+                                }
+                            });
+                    });
+                    self.render_bulk_visibility(ui);
+
+                    for (idx, depth) in self.visible_objects() {
+                        let is_selected = self.selected_object == Some(idx) || self.multi_selection.contains(&idx);
+
+                        ui.horizontal(|ui| {
+                            ui.add_space(depth as f32 * 16.0);
+
+                            let obj = &mut self.config.objects[idx];
+                            let mut eye = egui::RichText::new("👁");
+                            if !obj.enabled {
+                                eye = eye.weak().strikethrough();
+                            }
+                            if ui.add(egui::Button::new(eye).small())
+                                .on_hover_text(if obj.enabled { "Hide" } else { "Show" })
+                                .clicked() {
+                                obj.enabled = !obj.enabled;
+                                self.unsaved_changes = true;
+                            }
+
+                            let first_tag = obj.tags.first().cloned();
+                            let mut label = egui::RichText::new(&obj.name);
+                            if !obj.enabled {
+                                label = label.weak();
+                            }
+                            let response = ui.selectable_label(is_selected, label);
+                            if self.scroll_to_selected && self.selected_object == Some(idx) {
+                                response.scroll_to_me(None);
+                            }
+                            if response.clicked() {
+                                if ui.input(|i| i.modifiers.command) {
+                                    self.toggle_in_selection(idx);
+                                } else {
+                                    self.select(Some(idx));
+                                }
+                            }
+
+                            if let Some(tag) = first_tag {
+                                let chip = egui::Label::new(egui::RichText::new(format!("#{}", tag)).small().color(tag_color(&tag, ui.visuals())))
+                                    .sense(egui::Sense::click());
+                                if ui.add(chip).on_hover_text("Show only objects with this tag").clicked() {
+                                    self.object_filter = format!("tag:{}", tag);
+                                }
+                            }
+                        });
+                    }
+                    self.scroll_to_selected = false;
+
+                    if self.multi_selection.len() > 1 {
+                        ui.separator();
+                        self.render_arrange_tools(ui);
+                    }
+                    if let [a, b] = self.multi_selection.iter().copied().collect::<Vec<_>>()[..] {
+                        ui.separator();
+                        self.render_measurement(ui, a, b);
                     }
                 });
             });
 
-        // Central panel: Transform editor
-        CentralPanel::default().show(ctx, |ui| {
+        // Central panel: Transform editor, drawn over the viewport background
+        let [r, g, b, a] = srgb_to_linear(self.config.render.clear_color);
+        let background = egui::Rgba::from_rgba_unmultiplied(r, g, b, a);
+        let frame = egui::Frame::central_panel(&ctx.style()).fill(background.into());
+        CentralPanel::default().frame(frame).show(ctx, |ui| {
+            // Match the background rather than the theme so text stays readable
+            *ui.visuals_mut() = if background.intensity() > 0.5 {
+                egui::Visuals::light()
+            } else {
+                egui::Visuals::dark()
+            };
             if let Some(idx) = self.selected_object {
                 if idx < self.config.objects.len() {
                     self.render_transform_editor(ui, idx);
                 } else {
                     ui.label("Selected object index out of range");
-                    self.selected_object = None;
+                    self.select(None);
                 }
             } else {
                 ui.vertical_centered(|ui| {
@@ -133,184 +693,1935 @@ impl EditorState {
                     ui.label("Select an object from the left panel to edit its transform");
                 });
             }
+
+            if self.show_frame_stats {
+                self.draw_frame_stats(ui);
+            }
         });
+
+        self.handle_dropped_files(ctx);
+        self.watch_config_file(ctx);
+        self.autosave(ctx);
+        self.show_recovery_prompt(ctx);
+        self.show_delete_confirmation(ctx);
+        self.show_path_prompt(ctx);
+        self.show_paste_prompt(ctx);
+        self.show_unsaved_changes_prompt(ctx);
+
+        self.last_frame_time = frame_start.elapsed();
+        if self.show_frame_stats {
+            ctx.request_repaint();
+        }
     }
 
-    fn render_transform_editor(&mut self, ui: &mut Ui, idx: usize) {
-        // Clone the object name to avoid borrow issues
-        let obj_name = self.config.objects[idx].name.clone();
+    /// Notice edits to the config file made by other programs
+    fn watch_config_file(&mut self, ctx: &Context) {
+        if !self.watcher_started {
+            self.watcher_started = true;
+            match ConfigWatcher::new(&self.config_path, ctx) {
+                Ok(watcher) => self.watcher = Some(watcher),
+                Err(e) => self.log_message(LogLevel::Warning, format!("Not watching {}: {}", self.config_path, e)),
+            }
+        }
 
-        ui.heading(&obj_name);
-        ui.separator();
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        if !watcher.poll(ctx) {
+            return;
+        }
 
-        ui.add_space(10.0);
+        // Our own saves also trigger events; only react if the file differs from what we know
+        let modified = modified_time(&self.config_path);
+        if modified == self.known_modified {
+            return;
+        }
+        if self.auto_reload && !self.unsaved_changes {
+            self.reload();
+        } else {
+            self.file_changed_on_disk = true;
+        }
+    }
 
-        // Position section
-        ui.group(|ui| {
-            ui.heading("Position");
-            ui.add_space(5.0);
+    /// Write unsaved changes to the autosave file once per interval
+    fn autosave(&mut self, ctx: &Context) {
+        let Some(interval) = self.autosave_interval else {
+            return;
+        };
+        if !self.unsaved_changes {
+            self.last_autosave = Instant::now();
+            return;
+        }
 
-            ui.horizontal(|ui| {
-                ui.label("X:");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.position[0])
-                    .speed(0.01)
-                    .max_decimals(2)).changed() {
-                    self.unsaved_changes = true;
-                }
-            });
+        let elapsed = self.last_autosave.elapsed();
+        if elapsed < interval {
+            // Make sure an idle editor still wakes up to autosave
+            ctx.request_repaint_after(interval - elapsed);
+            return;
+        }
 
-            ui.horizontal(|ui| {
-                ui.label("Y:");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.position[1])
-                    .speed(0.01)
-                    .max_decimals(2)).changed() {
-                    self.unsaved_changes = true;
-                }
-            });
+        self.last_autosave = Instant::now();
+        let path = autosave_path(&self.config_path);
+        if let Err(e) = self.config.save(&path) {
+            self.log_message(LogLevel::Error, format!("Autosave failed: {}", e));
+        }
+    }
 
+    /// Offer to restore `recovery_path` in place of the loaded config
+    fn show_recovery_prompt(&mut self, ctx: &Context) {
+        let Some(path) = self.recovery_path.clone() else {
+            return;
+        };
+
+        let mut recover = None;
+        let modal = egui::Modal::new(egui::Id::new("autosave_recovery")).show(ctx, |ui| {
+            ui.heading("Recover Autosave");
+            ui.label(format!("{} is newer than {}.", path.display(), self.config_path));
+            ui.label("Recover it? The config file itself is not changed until you save.");
+            ui.add_space(10.0);
             ui.horizontal(|ui| {
-                ui.label("Z:");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.position[2])
-                    .speed(0.01)
-                    .max_decimals(2)).changed() {
-                    self.unsaved_changes = true;
+                if ui.button("Recover").clicked() {
+                    recover = Some(true);
+                }
+                if ui.button("Ignore").clicked() {
+                    recover = Some(false);
                 }
             });
         });
 
-        ui.add_space(10.0);
+        if recover.is_none() && !modal.should_close() {
+            return;
+        }
+        self.recovery_path = None;
+        if recover != Some(true) {
+            return;
+        }
 
-        // Rotation section
-        ui.group(|ui| {
-            ui.heading("Rotation (Euler Angles)");
-            ui.add_space(5.0);
+        match SceneConfig::load(&path) {
+            Ok(config) => {
+                self.config = config;
+                self.check_config();
+                self.select(None);
+                self.unsaved_changes = true;
+                self.log_message(LogLevel::Info, format!("Recovered {}", path.display()));
+            }
+            Err(e) => {
+                self.log_message(LogLevel::Error, format!("Recovery failed: {}", e));
+            }
+        }
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Yaw:  ");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.rotation_euler[0])
-                    .speed(1.0)
-                    .max_decimals(2)
-                    .suffix("°")).changed() {
-                    self.unsaved_changes = true;
-                }
-            });
+    /// Run `action` now, or ask first if it would discard unsaved changes
+    fn request_action(&mut self, ctx: &Context, action: PendingAction) {
+        if self.unsaved_changes {
+            self.pending_action = Some(action);
+        } else {
+            self.perform_action(ctx, action);
+        }
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Pitch:");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.rotation_euler[1])
-                    .speed(1.0)
-                    .max_decimals(2)
-                    .suffix("°")).changed() {
-                    self.unsaved_changes = true;
-                }
-            });
+    fn perform_action(&mut self, ctx: &Context, action: PendingAction) {
+        match action {
+            PendingAction::Reload => self.reload(),
+            PendingAction::Exit => {
+                self.save_session(ctx);
+                self.allow_close = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+            PendingAction::Open(path) => self.open_config(path),
+            PendingAction::NewScene(example) => self.new_scene(example),
+            PendingAction::Replace(config) => {
+                self.config = *config;
+                self.check_config();
+                self.select(None);
+                // The pasted config is not what is on disk
+                self.unsaved_changes = true;
+                self.log_message(LogLevel::Info, "Pasted config from clipboard".to_string());
+            }
+        }
+    }
 
-            ui.horizontal(|ui| {
-                ui.label("Roll: ");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.rotation_euler[2])
-                    .speed(1.0)
-                    .max_decimals(2)
-                    .suffix("°")).changed() {
-                    self.unsaved_changes = true;
+    fn copy_config(&mut self, ctx: &Context) {
+        match self.config.to_json() {
+            Ok(json) => {
+                ctx.copy_text(json);
+                self.log_message(LogLevel::Info, "Copied config to clipboard".to_string());
+            }
+            Err(e) => self.log_message(LogLevel::Error, format!("Copy failed: {}", e)),
+        }
+    }
+
+    /// Copy the world position or transform as text, and set the world position from pasted text
+    fn render_position_clipboard(&mut self, ui: &mut Ui, idx: usize) {
+        ui.horizontal(|ui| {
+            if ui.small_button("📋 Copy Position").on_hover_text("Copy the world position as \"x, y, z\"").clicked() {
+                let p = self.config.world_position(idx);
+                let precision = self.copy_precision;
+                ui.ctx().copy_text(format!("{:.*}, {:.*}, {:.*}", precision, p.x, precision, p.y, precision, p.z));
+            }
+            ui.add(DragValue::new(&mut self.copy_precision).range(0..=9).suffix(" decimals"));
+            if ui.small_button("Copy as JSON").on_hover_text("Copy the object's transform as config JSON").clicked() {
+                match serde_json::to_string_pretty(&self.config.objects[idx].transform) {
+                    Ok(json) => ui.ctx().copy_text(json),
+                    Err(e) => self.log_message(LogLevel::Error, format!("Copy failed: {}", e)),
                 }
-            });
+            }
         });
+        ui.horizontal(|ui| {
+            let response = ui.add(egui::TextEdit::singleline(&mut self.position_paste)
+                .hint_text("x, y, z")
+                .desired_width(160.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.small_button("Paste Position").on_hover_text("Paste \"x, y, z\" into the box, then apply it as the world position").clicked() || submitted {
+                match parse_position(&self.position_paste) {
+                    Some(position) => {
+                        self.keep_locked(|config| config.set_world_position(idx, Vec3::from(position)));
+                        self.position_paste.clear();
+                        self.unsaved_changes = true;
+                    }
+                    None => self.log_message(
+                        LogLevel::Warning,
+                        format!("\"{}\" is not a position; expected three numbers like 1.5, 0, -2", self.position_paste.trim()),
+                    ),
+                }
+            }
+        });
+    }
 
-        ui.add_space(10.0);
-
-        // Scale section
-        ui.group(|ui| {
-            ui.heading("Scale");
-            ui.add_space(5.0);
+    /// Modal with a text box to paste scene JSON into; applying it goes through the unsaved-changes prompt
+    fn show_paste_prompt(&mut self, ctx: &Context) {
+        let Some(mut text) = self.paste_buffer.take() else {
+            return;
+        };
 
-            ui.horizontal(|ui| {
-                ui.label("X:");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.scale[0])
-                    .speed(0.01)
-                    .max_decimals(2)).changed() {
-                    self.unsaved_changes = true;
-                }
+        let mut apply = false;
+        let mut cancelled = false;
+        let modal = egui::Modal::new(egui::Id::new("paste_config")).show(ctx, |ui| {
+            ui.heading("Paste Config");
+            ui.label("Paste scene JSON below (Ctrl/Cmd+V). It replaces the whole config.");
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                ui.add(egui::TextEdit::multiline(&mut text)
+                    .code_editor()
+                    .desired_rows(12)
+                    .desired_width(480.0));
             });
-
+            ui.add_space(10.0);
             ui.horizontal(|ui| {
-                ui.label("Y:");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.scale[1])
-                    .speed(0.01)
-                    .max_decimals(2)).changed() {
-                    self.unsaved_changes = true;
+                if ui.add_enabled(!text.trim().is_empty(), egui::Button::new("Apply")).clicked() {
+                    apply = true;
                 }
-            });
-
-            ui.horizontal(|ui| {
-                ui.label("Z:");
-                if ui.add(DragValue::new(&mut self.config.objects[idx].transform.scale[2])
-                    .speed(0.01)
-                    .max_decimals(2)).changed() {
-                    self.unsaved_changes = true;
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
                 }
             });
         });
 
-        ui.add_space(20.0);
+        if apply {
+            match SceneConfig::from_json(&text) {
+                Ok(config) => {
+                    self.request_action(ctx, PendingAction::Replace(Box::new(config)));
+                    return;
+                }
+                // Keep the dialog open so the JSON can be fixed
+                Err(e) => self.log_message(LogLevel::Error, format!("Paste failed: {}", e)),
+            }
+        } else if cancelled || modal.should_close() {
+            return;
+        }
+        self.paste_buffer = Some(text);
+    }
 
-        // Enabled checkbox
-        ui.horizontal(|ui| {
-            if ui.checkbox(&mut self.config.objects[idx].enabled, "Enabled").changed() {
-                self.unsaved_changes = true;
+    /// Load files dropped onto the window: models become new objects, scenes replace the config
+    fn handle_dropped_files(&mut self, ctx: &Context) {
+        let dropped: Vec<PathBuf> = ctx.input(|i| i.raw.dropped_files.iter().filter_map(|file| file.path.clone()).collect());
+        for path in dropped {
+            let extension = path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase());
+            match extension.as_deref() {
+                Some("gltf" | "glb" | "obj" | "stl") => self.add_model_object(&path),
+                Some("json") => self.request_action(ctx, PendingAction::Open(path.to_string_lossy().into_owned())),
+                _ => self.log_message(LogLevel::Warning, format!("Ignored dropped file {}: not a model or scene", path.display())),
             }
-        });
+        }
 
-        ui.add_space(20.0);
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("drop_hint")));
+            let screen = ctx.screen_rect();
+            painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                "Drop a .gltf/.glb/.obj/.stl model to add it, or a .json scene to open it",
+                egui::FontId::proportional(20.0),
+                egui::Color32::WHITE,
+            );
+        }
+    }
 
-        // Delete button
-        ui.horizontal(|ui| {
-            if ui.button("🗑 Delete Object").clicked() {
-                self.config.objects.remove(idx);
-                self.selected_object = None;
-                self.unsaved_changes = true;
-            }
-        });
+    /// Add and select a new object showing the model at `path`
+    fn add_model_object(&mut self, path: &std::path::Path) {
+        let stem = path.file_stem().map_or_else(|| "Model".to_string(), |s| s.to_string_lossy().into_owned());
+        let new_obj = SceneObject {
+            name: self.config.unique_name(&stem),
+            transform: Transform::default(),
+            enabled: true,
+            color: [1.0, 1.0, 1.0, 1.0],
+            opacity: 1.0,
+            parent: None,
+            model_path: Some(relative_model_path(&self.config_path, path)),
+            tags: Vec::new(),
+            instances: None,
+        };
+        self.log_message(LogLevel::Info, format!("Added {} for {}", new_obj.name, path.display()));
+        self.config.objects.push(new_obj);
+        self.select(Some(self.config.objects.len() - 1));
+        self.unsaved_changes = true;
     }
 
-    fn save(&mut self) {
-        match self.config.save(&self.config_path) {
-            Ok(_) => {
+    /// Start editing a blank or example scene under an untitled path next to the current config
+    fn new_scene(&mut self, example: bool) {
+        self.config = if example { SceneConfig::example() } else { SceneConfig::blank() };
+        self.config_path = untitled_path(&self.config_path);
+        self.unsaved_changes = false;
+        self.check_config();
+        self.select(None);
+        self.known_modified = None;
+        self.file_changed_on_disk = false;
+        self.watcher = None;
+        self.watcher_started = false;
+        self.log_message(LogLevel::Info, format!("New scene; Save writes {}", self.config_path));
+    }
+
+    /// Replace the config with another scene file and start editing that file
+    fn open_config(&mut self, path: String) {
+        match SceneConfig::load(&path) {
+            Ok(config) => {
+                self.config = config;
+                self.config_path = path;
                 self.unsaved_changes = false;
-                self.status_message = Some(format!("✓ Saved to {}", self.config_path));
+                self.check_config();
+                self.select(None);
+                self.models.clear_failures();
+                self.known_modified = modified_time(&self.config_path);
+                self.file_changed_on_disk = false;
+                // Watch the new file from the next frame on
+                self.watcher = None;
+                self.watcher_started = false;
+                self.log_message(LogLevel::Info, format!("Opened {}", self.config_path));
             }
             Err(e) => {
-                self.status_message = Some(format!("✗ Save failed: {}", e));
+                self.log_message(LogLevel::Error, format!("Open failed: {}", e));
             }
         }
     }
 
-    fn reload(&mut self) {
-        match SceneConfig::load(&self.config_path) {
+    /// Remember the selection and window size for the next launch on this config
+    fn save_session(&mut self, ctx: &Context) {
+        let session = EditorSession {
+            config_path: self.config_path.clone(),
+            selected_object: self
+                .selected_object
+                .and_then(|idx| self.config.objects.get(idx))
+                .map(|obj| obj.name.clone()),
+            window_size: ctx.input(|i| i.viewport().inner_rect).map(|rect| rect.size().into()),
+            theme: self.theme,
+        };
+        if let Err(e) = session.save() {
+            self.log_message(LogLevel::Warning, format!("Could not save session: {}", e));
+        }
+    }
+
+    /// Apply the parts of a saved session that live in the editor state
+    pub fn restore_session(&mut self, session: &EditorSession) {
+        if let Some(name) = &session.selected_object {
+            self.select(self.config.find_object(name));
+        }
+    }
+
+    /// Save / Discard / Cancel prompt for `pending_action`
+    fn show_unsaved_changes_prompt(&mut self, ctx: &Context) {
+        let Some(action) = self.pending_action.clone() else {
+            return;
+        };
+
+        let mut choice = None;
+        let modal = egui::Modal::new(egui::Id::new("unsaved_changes_prompt")).show(ctx, |ui| {
+            ui.heading("Unsaved Changes");
+            ui.label(format!("Save changes to {} before continuing?", self.config_path));
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button("💾 Save").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Discard").clicked() {
+                    choice = Some(false);
+                }
+                if ui.button("Cancel").clicked() {
+                    self.pending_action = None;
+                }
+            });
+        });
+
+        match choice {
+            Some(save) => {
+                self.pending_action = None;
+                if save {
+                    self.save();
+                    // Keep the edits if saving failed; the status message says why
+                    if self.unsaved_changes {
+                        return;
+                    }
+                }
+                self.perform_action(ctx, action);
+            }
+            None if modal.should_close() => self.pending_action = None,
+            None => {}
+        }
+    }
+
+    /// Objects shown in the list, in hierarchy order with their depth, after the filter
+    fn visible_objects(&self) -> Vec<(usize, usize)> {
+        let query = self.object_filter.to_lowercase();
+        self.config
+            .hierarchy_order()
+            .into_iter()
+            .filter(|&(idx, _)| {
+                let obj = &self.config.objects[idx];
+                matches_filter(obj, &query) && self.enabled_filter.matches(obj.enabled)
+            })
+            .collect()
+    }
+
+    /// Buttons enabling or disabling all, listed or selected objects at once
+    fn render_bulk_visibility(&mut self, ui: &mut Ui) {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.label("Show/hide:");
+            let groups = [
+                ("All", "every object"),
+                ("Listed", "the objects the filters currently list"),
+                ("Selected", "the selected objects"),
+            ];
+            for (group, (label, description)) in groups.iter().enumerate() {
+                if group == 2 && self.multi_selection.is_empty() {
+                    continue;
+                }
+                ui.menu_button(*label, |ui| {
+                    if ui.button("👁 Enable").on_hover_text(format!("Enable {}", description)).clicked() {
+                        action = Some((group, true));
+                        ui.close_menu();
+                    }
+                    if ui.button("Disable").on_hover_text(format!("Disable {}", description)).clicked() {
+                        action = Some((group, false));
+                        ui.close_menu();
+                    }
+                });
+            }
+        });
+
+        let Some((group, enabled)) = action else {
+            return;
+        };
+        let indices: Vec<usize> = match group {
+            0 => (0..self.config.objects.len()).collect(),
+            1 => self.visible_objects().into_iter().map(|(idx, _)| idx).collect(),
+            _ => self.multi_selection.iter().copied().collect(),
+        };
+        let changed = self.config.set_enabled(&indices, enabled);
+        if changed > 0 {
+            self.unsaved_changes = true;
+        }
+        let verb = if enabled { "Enabled" } else { "Disabled" };
+        self.log_message(LogLevel::Info, format!("{} {} object(s)", verb, changed));
+    }
+
+    /// Up/Down or Tab/Shift+Tab step the selection through the list, wrapping at the ends
+    ///
+    /// Ignored while any widget has keyboard focus (so typing and Tab focus
+    /// navigation keep working) and while a dialog is open.
+    fn handle_list_navigation(&mut self, ctx: &Context) {
+        if self.shortcuts_blocked(ctx) {
+            return;
+        }
+
+        let step = ctx.input_mut(|i| {
+            if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) || i.consume_key(egui::Modifiers::NONE, egui::Key::Tab) {
+                1
+            } else if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) || i.consume_key(egui::Modifiers::SHIFT, egui::Key::Tab) {
+                -1
+            } else {
+                0
+            }
+        });
+        if step == 0 {
+            return;
+        }
+
+        let order: Vec<usize> = self.visible_objects().into_iter().map(|(idx, _)| idx).collect();
+        if order.is_empty() {
+            return;
+        }
+        let position = self.selected_object.and_then(|selected| order.iter().position(|&idx| idx == selected));
+        let next = match position {
+            Some(pos) => (pos as isize + step).rem_euclid(order.len() as isize) as usize,
+            None if step > 0 => 0,
+            None => order.len() - 1,
+        };
+        self.select(Some(order[next]));
+        self.scroll_to_selected = true;
+    }
+
+    /// Whether editor shortcuts should stand aside: a dialog is open or a widget has keyboard focus
+    fn shortcuts_blocked(&self, ctx: &Context) -> bool {
+        let dialog_open = self.pending_delete.is_some()
+            || self.pending_action.is_some()
+            || self.path_prompt.is_some()
+            || self.paste_buffer.is_some()
+            || self.recovery_path.is_some();
+        dialog_open || ctx.memory(|m| m.focused().is_some())
+    }
+
+    /// Alt+←/→ nudge the selected object along X, Alt+↑/↓ along Y and Alt+PgUp/PgDn along Z
+    fn handle_nudge_keys(&mut self, ctx: &Context) {
+        if self.selected_object.is_none() || self.shortcuts_blocked(ctx) {
+            return;
+        }
+        const KEYS: [(egui::Key, usize, f32); 6] = [
+            (egui::Key::ArrowLeft, 0, -1.0),
+            (egui::Key::ArrowRight, 0, 1.0),
+            (egui::Key::ArrowDown, 1, -1.0),
+            (egui::Key::ArrowUp, 1, 1.0),
+            (egui::Key::PageDown, 2, -1.0),
+            (egui::Key::PageUp, 2, 1.0),
+        ];
+        let pressed: Vec<_> = ctx.input_mut(|i| {
+            KEYS.iter()
+                .filter(|(key, _, _)| i.consume_key(egui::Modifiers::ALT, *key))
+                .map(|&(_, axis, sign)| (axis, sign))
+                .collect()
+        });
+        for (axis, sign) in pressed {
+            self.nudge(0, axis, sign);
+        }
+    }
+
+    /// Add one nudge step to the selected object's position (`section` 0) or rotation (1)
+    ///
+    /// `axis` indexes `position` or `rotation_euler`; locked axes are left alone.
+    fn nudge(&mut self, section: usize, axis: usize, sign: f32) {
+        let Some(idx) = self.selected_object else {
+            return;
+        };
+        if self.axis_locks[section][axis] {
+            return;
+        }
+        let transform = &mut self.config.objects[idx].transform;
+        match section {
+            0 => transform.position[axis] += sign * self.nudge_step[axis],
+            _ => transform.rotation_euler[axis] += sign * self.nudge_angle,
+        }
+        self.unsaved_changes = true;
+    }
+
+    /// Run a tool that rewrites transforms, then put every locked axis back
+    ///
+    /// Lets paste, mirror, snap, align/distribute and CSV import honor the locks
+    /// the same way the fields and nudges do, without each tool knowing about them.
+    fn keep_locked<R>(&mut self, edit: impl FnOnce(&mut SceneConfig) -> R) -> R {
+        let before: Vec<Transform> = self.config.objects.iter().map(|obj| obj.transform.clone()).collect();
+        let result = edit(&mut self.config);
+        let [position, rotation, scale] = self.axis_locks;
+        for (obj, before) in self.config.objects.iter_mut().zip(&before) {
+            for axis in 0..3 {
+                if position[axis] {
+                    obj.transform.position[axis] = before.position[axis];
+                }
+                if rotation[axis] {
+                    obj.transform.rotation_euler[axis] = before.rotation_euler[axis];
+                }
+                if scale[axis] {
+                    obj.transform.scale[axis] = before.scale[axis];
+                }
+            }
+        }
+        result
+    }
+
+    /// Make `idx` the only selected object (or clear the selection)
+    fn select(&mut self, idx: Option<usize>) {
+        self.selected_object = idx;
+        self.multi_selection = idx.into_iter().collect();
+        self.name_edit = None;
+    }
+
+    /// Add or remove an object from a multi-selection
+    fn toggle_in_selection(&mut self, idx: usize) {
+        if let Some(primary) = self.selected_object {
+            self.multi_selection.insert(primary);
+        }
+        if self.multi_selection.remove(&idx) {
+            if self.selected_object == Some(idx) {
+                self.selected_object = self.multi_selection.iter().next().copied();
+            }
+        } else {
+            self.multi_selection.insert(idx);
+            self.selected_object = Some(idx);
+        }
+    }
+
+    /// Align/distribute buttons for the multi-selection
+    fn render_arrange_tools(&mut self, ui: &mut Ui) {
+        let selection: Vec<usize> = self
+            .multi_selection
+            .iter()
+            .copied()
+            .filter(|&idx| idx < self.config.objects.len())
+            .collect();
+
+        ui.label(format!("Arrange {} objects (disabled ones are skipped)", selection.len()));
+        egui::Grid::new("arrange_tools").show(ui, |ui| {
+            for (axis, label) in ["X", "Y", "Z"].iter().enumerate() {
+                ui.label(format!("{}:", label));
+                for (mode, text) in [(AlignMode::Min, "Min"), (AlignMode::Center, "Center"), (AlignMode::Max, "Max")] {
+                    if ui.small_button(text).on_hover_text(format!("Align {} to the {}", label, text.to_lowercase())).clicked()
+                        && self.keep_locked(|config| config.align(&selection, axis, mode))
+                    {
+                        self.unsaved_changes = true;
+                    }
+                }
+                if ui.small_button("Distribute").on_hover_text(format!("Space evenly along {}", label)).clicked()
+                    && self.keep_locked(|config| config.distribute(&selection, axis))
+                {
+                    self.unsaved_changes = true;
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    /// Distance and per-axis offset between the world positions of two objects
+    fn render_measurement(&self, ui: &mut Ui, a: usize, b: usize) {
+        if a >= self.config.objects.len() || b >= self.config.objects.len() {
+            return;
+        }
+        let delta = self.config.world_position(b) - self.config.world_position(a);
+        let (from, to) = (&self.config.objects[a].name, &self.config.objects[b].name);
+
+        ui.label(format!("Measure {} → {}", from, to));
+        egui::Grid::new("measurement").show(ui, |ui| {
+            ui.label("Distance:");
+            ui.monospace(format!("{:.4}", delta.length()));
+            ui.end_row();
+            for (label, value) in ["ΔX:", "ΔY:", "ΔZ:"].iter().zip(delta.to_array()) {
+                ui.label(*label);
+                ui.monospace(format!("{:+.4}", value));
+                ui.end_row();
+            }
+        });
+        if ui.small_button("📋 Copy").on_hover_text("Copy the measurement as text").clicked() {
+            ui.ctx().copy_text(format!(
+                "{} -> {}: distance {:.4} (dx {:.4}, dy {:.4}, dz {:.4})",
+                from, to, delta.length(), delta.x, delta.y, delta.z
+            ));
+        }
+    }
+
+    /// Config path with its extension swapped, as a default for file operations
+    fn sibling_path(&self, extension: &str) -> String {
+        std::path::Path::new(&self.config_path)
+            .with_extension(extension)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Modal for editing the path of `path_prompt` and running it
+    fn show_path_prompt(&mut self, ctx: &Context) {
+        let Some((action, mut path)) = self.path_prompt.take() else {
+            return;
+        };
+
+        let mut run = false;
+        let mut cancelled = false;
+        let modal = egui::Modal::new(egui::Id::new("path_prompt")).show(ctx, |ui| {
+            ui.heading(action.title());
+            ui.horizontal(|ui| {
+                ui.label("Path:");
+                let response = ui.add(egui::TextEdit::singleline(&mut path).desired_width(320.0));
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    run = true;
+                }
+            });
+            if matches!(action, PathAction::ExportGltf | PathAction::ImportGltf) {
+                ui.horizontal(|ui| {
+                    ui.label("File convention:");
+                    for up_axis in UpAxis::ALL {
+                        ui.selectable_value(&mut self.gltf_up_axis, up_axis, up_axis.label());
+                    }
+                });
+            }
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(action.title()).clicked() {
+                    run = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+        if run {
+            self.run_path_action(action, &path);
+        } else if !cancelled && !modal.should_close() {
+            self.path_prompt = Some((action, path));
+        }
+    }
+
+    fn run_path_action(&mut self, action: PathAction, path: &str) {
+        let result = match action {
+            PathAction::ExportGltf => self
+                .config
+                .export_gltf(path, self.gltf_up_axis)
+                .map(|_| format!("Exported {}", path)),
+            PathAction::ImportGltf => self.config.import_gltf_nodes(path, self.gltf_up_axis).map(|count| {
+                if count > 0 {
+                    self.unsaved_changes = true;
+                }
+                format!("Imported {} node(s) from {}", count, path)
+            }),
+            PathAction::ExportCsv => self.config.export_csv(path).map(|_| format!("Exported {}", path)),
+            PathAction::ImportCsv => self.keep_locked(|config| config.import_csv(path)).map(|(updated, unmatched)| {
+                if updated > 0 {
+                    self.unsaved_changes = true;
+                }
+                if unmatched > 0 {
+                    format!("Updated {} object(s) from {}; {} row(s) matched no object", updated, path, unmatched)
+                } else {
+                    format!("Updated {} object(s) from {}", updated, path)
+                }
+            }),
+        };
+        match result {
+            Ok(message) => self.log_message(LogLevel::Info, message),
+            Err(e) => self.log_message(LogLevel::Error, format!("{} failed: {}", action.title(), e)),
+        }
+    }
+
+    /// Modal asking whether to delete `pending_delete`
+    fn show_delete_confirmation(&mut self, ctx: &Context) {
+        let Some(idx) = self.pending_delete else {
+            return;
+        };
+        let Some(obj) = self.config.objects.get(idx) else {
+            self.pending_delete = None;
+            return;
+        };
+
+        let name = obj.name.clone();
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let modal = egui::Modal::new(egui::Id::new("delete_confirmation")).show(ctx, |ui| {
+            ui.heading("Delete Object");
+            ui.label(format!("Delete \"{}\"? This cannot be undone.", name));
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button("🗑 Delete").clicked() {
+                    confirmed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+        if confirmed {
+            self.config.remove_object(idx);
+            self.select(None);
+            self.unsaved_changes = true;
+            self.log_message(LogLevel::Info, format!("Deleted {}", name));
+            self.pending_delete = None;
+        } else if cancelled || modal.should_close() {
+            self.pending_delete = None;
+        }
+    }
+
+    /// Frame time and FPS in the top-right corner of the central panel
+    fn draw_frame_stats(&self, ui: &Ui) {
+        let dt = ui.ctx().input(|i| i.stable_dt);
+        let fps = if dt > 0.0 { 1.0 / dt } else { 0.0 };
+        let rect = ui.max_rect();
+        let (drawn, culled) = self.cull_counts(rect.aspect_ratio());
+        let text = format!(
+            "{:.0} FPS ({:.1} ms)\nUI build: {:.2} ms\nCockpit view: {} drawn, {} culled",
+            fps,
+            dt * 1000.0,
+            self.last_frame_time.as_secs_f64() * 1000.0,
+            drawn,
+            culled
+        );
+        ui.painter().text(
+            rect.right_top() + egui::vec2(-8.0, 8.0),
+            egui::Align2::RIGHT_TOP,
+            text,
+            egui::FontId::monospace(12.0),
+            ui.visuals().weak_text_color(),
+        );
+    }
+
+    /// Enabled objects inside and outside the cockpit camera's frustum, as (drawn, culled)
+    ///
+    /// Objects are tested by their loaded model's bounding sphere, or by their
+    /// origin while the model is not loaded.
+    fn cull_counts(&self, aspect: f32) -> (usize, usize) {
+        let camera = &self.config.camera.cockpit;
+        let frustum = Frustum::from_view_projection(camera.projection_matrix(aspect) * camera.view_matrix());
+        let base = self.config.model.base_matrix();
+        let (mut drawn, mut culled) = (0, 0);
+        for (idx, obj) in self.config.objects.iter().enumerate().filter(|(_, obj)| obj.enabled) {
+            let world = self.config.world_matrix(idx);
+            let model = obj
+                .model_path
+                .as_deref()
+                .and_then(|model_path| self.models.get(&resolve_model_path(&self.config_path, model_path)));
+            let (center, radius) = match model {
+                Some(model) => model.transformed_bounding_sphere(world * base),
+                None => (world.transform_point3(Vec3::ZERO), 0.0),
+            };
+            if frustum.intersects_sphere(center, radius) {
+                drawn += 1;
+            } else {
+                culled += 1;
+            }
+        }
+        (drawn, culled)
+    }
+
+    fn render_transform_editor(&mut self, ui: &mut Ui, idx: usize) {
+        // Clone the object name to avoid borrow issues
+        let obj_name = self.config.objects[idx].name.clone();
+
+        ui.horizontal(|ui| {
+            ui.heading(&obj_name);
+            ui.toggle_value(&mut self.exact_entry, "⌨ Type Values")
+                .on_hover_text("Edit transform fields as text: type a number or an expression like 2*1.5, then press Enter");
+            if ui.button("↺ Reset Transform").clicked() {
+                let transform = &mut self.config.objects[idx].transform;
+                let default = Transform::default();
+                reset_unlocked(&mut transform.position, default.position, self.axis_locks[0]);
+                reset_unlocked(&mut transform.rotation_euler, default.rotation_euler, self.axis_locks[1]);
+                reset_unlocked(&mut transform.scale, default.scale, self.axis_locks[2]);
+                self.unsaved_changes = true;
+            }
+        });
+
+        // Name
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            let mut text = self.name_edit.clone().unwrap_or_else(|| obj_name.clone());
+            let response = ui.text_edit_singleline(&mut text);
+            if response.changed() {
+                self.name_edit = Some(text);
+            }
+            if response.lost_focus() {
+                if let Some(requested) = self.name_edit.take().filter(|name| !name.trim().is_empty()) {
+                    let requested = requested.trim();
+                    let name = self.config.rename_object(idx, requested);
+                    if name != obj_name {
+                        self.unsaved_changes = true;
+                    }
+                    if name != requested {
+                        self.log_message(LogLevel::Warning, format!("\"{}\" is taken; renamed to {}", requested, name));
+                    }
+                }
+            }
+        });
+
+        // Tags
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Tags:");
+            let mut removed = None;
+            for (i, tag) in self.config.objects[idx].tags.iter().enumerate() {
+                let chip = egui::Button::new(egui::RichText::new(format!("#{} ✖", tag)).color(tag_color(tag, ui.visuals()))).small();
+                if ui.add(chip).on_hover_text("Remove tag").clicked() {
+                    removed = Some(i);
+                }
+            }
+            if let Some(i) = removed {
+                self.config.objects[idx].tags.remove(i);
+                self.unsaved_changes = true;
+            }
+
+            let response = ui.add(egui::TextEdit::singleline(&mut self.tag_input)
+                .hint_text("new tag")
+                .desired_width(100.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if (submitted || ui.small_button("➕").clicked()) && self.config.add_tag(idx, &self.tag_input) {
+                self.tag_input.clear();
+                self.unsaved_changes = true;
+            }
+        });
+        ui.separator();
+
+        ui.add_space(10.0);
+
+        // Position section
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Position");
+                if ui.small_button("↺").on_hover_text("Reset position").clicked() {
+                    reset_unlocked(&mut self.config.objects[idx].transform.position, [0.0, 0.0, 0.0], self.axis_locks[0]);
+                    self.unsaved_changes = true;
+                }
+            });
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.label("X:");
+                let locked = self.axis_locks[0][0];
+                let value = &mut self.config.objects[idx].transform.position[0];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 0, 0), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 0.01))
+                        .max_decimals(2)).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[0][0]);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Y:");
+                let locked = self.axis_locks[0][1];
+                let value = &mut self.config.objects[idx].transform.position[1];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 0, 1), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 0.01))
+                        .max_decimals(2)).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[0][1]);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Z:");
+                let locked = self.axis_locks[0][2];
+                let value = &mut self.config.objects[idx].transform.position[2];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 0, 2), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 0.01))
+                        .max_decimals(2)).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[0][2]);
+            });
+
+            self.render_position_clipboard(ui, idx);
+        });
+
+        ui.add_space(10.0);
+
+        // Rotation section
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Rotation (Euler Angles)");
+                if ui.small_button("↺").on_hover_text("Reset rotation").clicked() {
+                    reset_unlocked(&mut self.config.objects[idx].transform.rotation_euler, [0.0, 0.0, 0.0], self.axis_locks[1]);
+                    self.unsaved_changes = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Order:");
+                let transform = &mut self.config.objects[idx].transform;
+                egui::ComboBox::from_id_salt("object_euler_order")
+                    .selected_text(&transform.order)
+                    .show_ui(ui, |ui| {
+                        for order in EULER_ORDERS {
+                            if ui.selectable_value(&mut transform.order, order.to_string(), order).changed() {
+                                self.unsaved_changes = true;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("Axis order the rotations are applied in (X = pitch, Y = yaw, Z = roll)");
+            });
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Yaw:  ");
+                let locked = self.axis_locks[1][0];
+                let value = &mut self.config.objects[idx].transform.rotation_euler[0];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 1, 0), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 1.0))
+                        .max_decimals(2)
+                        .suffix("°")).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[1][0]);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Pitch:");
+                let locked = self.axis_locks[1][1];
+                let value = &mut self.config.objects[idx].transform.rotation_euler[1];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 1, 1), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 1.0))
+                        .max_decimals(2)
+                        .suffix("°")).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[1][1]);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Roll: ");
+                let locked = self.axis_locks[1][2];
+                let value = &mut self.config.objects[idx].transform.rotation_euler[2];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 1, 2), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 1.0))
+                        .max_decimals(2)
+                        .suffix("°")).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[1][2]);
+            });
+        });
+
+        ui.add_space(10.0);
+
+        // Scale section
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Scale");
+                if ui.small_button("↺").on_hover_text("Reset scale").clicked() {
+                    reset_unlocked(&mut self.config.objects[idx].transform.scale, [1.0, 1.0, 1.0], self.axis_locks[2]);
+                    self.unsaved_changes = true;
+                }
+                if self.config.objects[idx].transform.skews_normals() {
+                    ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                        .on_hover_text("Non-uniform scale on a rotated object: lighting needs the normal matrix \
+                            (see World Matrix) rather than the model matrix to shade it correctly");
+                }
+            });
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.label("X:");
+                let locked = self.axis_locks[2][0];
+                let value = &mut self.config.objects[idx].transform.scale[0];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 2, 0), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 0.01))
+                        .max_decimals(2)).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[2][0]);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Y:");
+                let locked = self.axis_locks[2][1];
+                let value = &mut self.config.objects[idx].transform.scale[1];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 2, 1), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 0.01))
+                        .max_decimals(2)).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[2][1]);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Z:");
+                let locked = self.axis_locks[2][2];
+                let value = &mut self.config.objects[idx].transform.scale[2];
+                let changed = if self.exact_entry {
+                    ui.add_enabled_ui(!locked, |ui| exact_field(ui, ("exact", 2, 2), value)).inner
+                } else {
+                    ui.add_enabled(!locked, DragValue::new(value)
+                        .speed(modifier_speed(ui, 0.01))
+                        .max_decimals(2)).on_hover_text(STEP_HINT).changed()
+                };
+                if changed {
+                    self.unsaved_changes = true;
+                }
+                lock_toggle(ui, &mut self.axis_locks[2][2]);
+            });
+        });
+
+        ui.add_space(10.0);
+
+        // Pivot section
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Pivot");
+                if ui.small_button("↺").on_hover_text("Move the pivot back to the origin").clicked() {
+                    self.config.objects[idx].transform.pivot = [0.0, 0.0, 0.0];
+                    self.unsaved_changes = true;
+                }
+                // Mesh bounding-box center in object space, through the model's base transform
+                let geometry_center = self.selected_model_path().and_then(|path| self.models.get(&path)).map(|model| {
+                    self.config.model.base_matrix().transform_point3((model.bounds_min + model.bounds_max) / 2.0)
+                });
+                if ui.add_enabled(geometry_center.is_some(), egui::Button::new("⌖ Center on Geometry").small())
+                    .on_hover_text("Put the pivot at the center of the model's bounding box without moving the model")
+                    .on_disabled_hover_text("Needs a loaded model")
+                    .clicked()
+                {
+                    if let Some(center) = geometry_center {
+                        self.config.objects[idx].transform.set_pivot_in_place(center);
+                        self.unsaved_changes = true;
+                    }
+                }
+            });
+            ui.label("Local point that rotation and scale happen around");
+            ui.horizontal(|ui| {
+                for (axis, label) in ["X:", "Y:", "Z:"].iter().enumerate() {
+                    ui.label(*label);
+                    if ui.add(DragValue::new(&mut self.config.objects[idx].transform.pivot[axis])
+                        .speed(modifier_speed(ui, 0.01))
+                        .max_decimals(3)).on_hover_text(STEP_HINT).changed() {
+                        self.unsaved_changes = true;
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        // Relative moves by a fixed step
+        ui.collapsing("Nudge", |ui| {
+            egui::Grid::new("nudge_grid").show(ui, |ui| {
+                for (axis, label) in ["X", "Y", "Z"].iter().enumerate() {
+                    ui.label(format!("Move {}:", label));
+                    ui.add(DragValue::new(&mut self.nudge_step[axis])
+                        .speed(0.01)
+                        .range(0.0..=f32::MAX)
+                        .max_decimals(3));
+                    if ui.small_button("−").clicked() {
+                        self.nudge(0, axis, -1.0);
+                    }
+                    if ui.small_button("+").clicked() {
+                        self.nudge(0, axis, 1.0);
+                    }
+                    ui.end_row();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Rotate by:");
+                ui.add(DragValue::new(&mut self.nudge_angle)
+                    .speed(1.0)
+                    .range(0.0..=360.0)
+                    .suffix("°"));
+            });
+            ui.horizontal(|ui| {
+                // rotation_euler order: yaw, pitch, roll
+                for (axis, label) in ["Yaw", "Pitch", "Roll"].iter().enumerate() {
+                    ui.label(*label);
+                    if ui.small_button("−").clicked() {
+                        self.nudge(1, axis, -1.0);
+                    }
+                    if ui.small_button("+").clicked() {
+                        self.nudge(1, axis, 1.0);
+                    }
+                }
+            });
+            ui.weak("Alt+←/→, Alt+↑/↓ and Alt+PgUp/PgDn nudge along X, Y and Z. Locked axes are skipped.");
+        });
+
+        ui.add_space(10.0);
+
+        // Random copies of this object
+        ui.collapsing("Scatter", |ui| {
+            let settings = &mut self.scatter;
+            egui::Grid::new("scatter_grid").show(ui, |ui| {
+                ui.label("Count:");
+                ui.add(DragValue::new(&mut settings.count).range(1..=10_000));
+                ui.end_row();
+                ui.label("X range:");
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut settings.min[0]).speed(0.1).max_decimals(2));
+                    ui.label("to");
+                    ui.add(DragValue::new(&mut settings.max[0]).speed(0.1).max_decimals(2));
+                });
+                ui.end_row();
+                ui.label("Z range:");
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut settings.min[1]).speed(0.1).max_decimals(2));
+                    ui.label("to");
+                    ui.add(DragValue::new(&mut settings.max[1]).speed(0.1).max_decimals(2));
+                });
+                ui.end_row();
+                ui.label("Scale variance:");
+                ui.add(DragValue::new(&mut settings.scale_variance).speed(0.01).range(0.0..=0.99).prefix("± "));
+                ui.end_row();
+                ui.label("Seed:");
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut settings.seed));
+                    if ui.small_button("🎲").on_hover_text("Pick a new seed").clicked() {
+                        settings.seed = SystemTime::now()
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .map_or(0, |d| d.as_nanos() as u64);
+                    }
+                });
+                ui.end_row();
+            });
+            if ui.button("Scatter copies").on_hover_text("Add copies at random X/Z positions with random yaw; the height is kept").clicked() {
+                let added = self.config.scatter(idx, &self.scatter);
+                self.unsaved_changes = true;
+                self.log_message(LogLevel::Info, format!("Scattered {} copies of {}", added.len(), obj_name));
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Mirror across a plane through the origin
+        ui.horizontal(|ui| {
+            ui.label("Mirror across:");
+            for (axis, label) in ["X = 0", "Y = 0", "Z = 0"].iter().enumerate() {
+                if ui.button(*label)
+                    .on_hover_text("Reflect the object across this plane (of its parent, for child objects); flips the scale on that axis")
+                    .clicked()
+                {
+                    self.keep_locked(|config| config.objects[idx].transform.mirror(axis));
+                    self.unsaved_changes = true;
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Snap onto another object
+        ui.horizontal(|ui| {
+            ui.label("Snap to:");
+            let target_name = self
+                .snap_target
+                .and_then(|target| self.config.objects.get(target))
+                .map_or("(choose)", |obj| obj.name.as_str());
+            egui::ComboBox::from_id_salt("snap_target")
+                .selected_text(target_name)
+                .show_ui(ui, |ui| {
+                    for (other, obj) in self.config.objects.iter().enumerate() {
+                        if other != idx {
+                            ui.selectable_value(&mut self.snap_target, Some(other), &obj.name);
+                        }
+                    }
+                });
+            for (axis, label) in ["X", "Y", "Z"].iter().enumerate() {
+                ui.checkbox(&mut self.snap_axes[axis], *label);
+            }
+            ui.checkbox(&mut self.snap_rotation, "Rotation");
+
+            let target = self.snap_target.filter(|&target| target != idx && target < self.config.objects.len());
+            if ui.add_enabled(target.is_some(), egui::Button::new("Snap")).clicked() {
+                if let Some(target) = target {
+                    let (axes, rotation) = (self.snap_axes, self.snap_rotation);
+                    self.keep_locked(|config| config.snap_to(idx, target, axes, rotation));
+                    self.unsaved_changes = true;
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("World Matrix", |ui| {
+            matrix_grid(ui, "world_matrix", &self.config.world_matrix(idx));
+            ui.add_space(5.0);
+            ui.label("Normal matrix (local, inverse-transpose):");
+            let normal = self.config.objects[idx].transform.normal_matrix();
+            matrix_grid(ui, "normal_matrix", &Mat4::from_mat3(normal));
+        });
+
+        ui.add_space(10.0);
+
+        // Parent selection
+        ui.horizontal(|ui| {
+            ui.label("Parent:");
+            let current = self.config.objects[idx].parent.clone().unwrap_or_else(|| "(none)".to_string());
+            let mut new_parent = None;
+            egui::ComboBox::from_id_salt("parent_combo")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(self.config.objects[idx].parent.is_none(), "(none)").clicked() {
+                        new_parent = Some(None);
+                    }
+                    for other in 0..self.config.objects.len() {
+                        if other == idx {
+                            continue;
+                        }
+                        let name = &self.config.objects[other].name;
+                        let is_parent = self.config.objects[idx].parent.as_deref() == Some(name.as_str());
+                        let valid = self.config.can_parent(idx, other);
+                        if ui.add_enabled(valid, egui::SelectableLabel::new(is_parent, name)).clicked() {
+                            new_parent = Some(Some(other));
+                        }
+                    }
+                });
+
+            if let Some(parent) = new_parent {
+                match self.config.set_parent(idx, parent) {
+                    Ok(()) => self.unsaved_changes = true,
+                    Err(e) => self.log_message(LogLevel::Error, e),
+                }
+            }
+        });
+
+        // Base color
+        ui.horizontal(|ui| {
+            ui.label("Color:");
+            if srgba_edit(ui, &mut self.config.objects[idx].color) {
+                self.unsaved_changes = true;
+            }
+        });
+
+        // Opacity
+        ui.horizontal(|ui| {
+            ui.label("Opacity:");
+            if ui.add(egui::Slider::new(&mut self.config.objects[idx].opacity, 0.0..=1.0)).changed() {
+                self.unsaved_changes = true;
+            }
+        });
+
+        // Instancing
+        ui.horizontal(|ui| {
+            let mut repeat = self.config.objects[idx].instances.is_some();
+            if ui.checkbox(&mut repeat, "Repeat").on_hover_text("Draw several copies laid out in even steps").changed() {
+                self.config.objects[idx].instances = repeat.then_some(InstanceSpec {
+                    count: 2,
+                    offset: [1.0, 0.0, 0.0],
+                    rotation_euler: [0.0; 3],
+                });
+                self.unsaved_changes = true;
+            }
+            if let Some(spec) = &mut self.config.objects[idx].instances {
+                ui.label("Count:");
+                if ui.add(DragValue::new(&mut spec.count).range(1..=10_000)).changed() {
+                    self.unsaved_changes = true;
+                }
+            }
+        });
+        if let Some(spec) = &mut self.config.objects[idx].instances {
+            let mut changed = false;
+            ui.horizontal(|ui| {
+                ui.label("Step offset:");
+                for value in &mut spec.offset {
+                    changed |= ui.add(DragValue::new(value)
+                        .speed(modifier_speed(ui, 0.01))
+                        .max_decimals(3)).on_hover_text(STEP_HINT).changed();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Step rotation (yaw, pitch, roll):");
+                for value in &mut spec.rotation_euler {
+                    changed |= ui.add(DragValue::new(value)
+                        .speed(modifier_speed(ui, 1.0))
+                        .suffix("°")).on_hover_text(STEP_HINT).changed();
+                }
+            });
+            if changed {
+                self.unsaved_changes = true;
+            }
+        }
+
+        // Model path
+        ui.horizontal(|ui| {
+            ui.label("Model:");
+            let mut path = self.config.objects[idx].model_path.clone().unwrap_or_default();
+            if ui.add(egui::TextEdit::singleline(&mut path).hint_text("path/to/model.glb, .obj or .stl")).changed() {
+                self.config.objects[idx].model_path = if path.is_empty() { None } else { Some(path) };
+                self.unsaved_changes = true;
+            }
+
+            // Switch between models already parsed this session, e.g. to compare variants
+            let loaded = self.models.loaded_paths();
+            if !loaded.is_empty() {
+                let current = self.selected_model_path();
+                let mut chosen = None;
+                egui::ComboBox::from_id_salt("loaded_models")
+                    .selected_text("Loaded…")
+                    .show_ui(ui, |ui| {
+                        for path in loaded {
+                            let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+                            let is_current = current.as_deref() == Some(path);
+                            if ui.selectable_label(is_current, name).on_hover_text(path.display().to_string()).clicked() && !is_current {
+                                chosen = Some(relative_model_path(&self.config_path, path));
+                            }
+                        }
+                    });
+                if let Some(model_path) = chosen {
+                    self.config.objects[idx].model_path = Some(model_path);
+                    self.unsaved_changes = true;
+                }
+            }
+        });
+
+        // Model statistics
+        if let Some(path) = self.selected_model_path() {
+            let preview = if self.simplify_preview {
+                Some(self.models.preview(&path, self.simplify_ratio, ui.ctx()).map(|model| (model.vertex_count(), model.triangle_count())))
+            } else {
+                None
+            };
+            // Full-mesh counts while the simplified copy is still being built
+            let with_preview = |full: usize, preview: Option<Option<usize>>| match preview {
+                Some(Some(preview)) => format!("{} ({} in preview)", full, preview),
+                Some(None) => format!("{} (simplifying…)", full),
+                None => full.to_string(),
+            };
+            if let Some(model) = self.models.get(&path) {
+                let mut normalize = false;
+                let instance_count = self.config.objects[idx].instance_count();
+                let (sphere_center, sphere_radius) = model
+                    .transformed_bounding_sphere(self.config.world_matrix(idx) * self.config.model.base_matrix());
+                ui.collapsing("Model Info", |ui| {
+                    let scaled = model.size() * self.config.model.combined_scale();
+                    let target = self.config.model.target_length;
+                    egui::Grid::new("model_info").show(ui, |ui| {
+                        ui.label("Vertices:");
+                        ui.label(with_preview(model.vertex_count(), preview.map(|counts| counts.map(|(vertices, _)| vertices))));
+                        ui.end_row();
+                        ui.label("Triangles:");
+                        ui.label(with_preview(model.triangle_count(), preview.map(|counts| counts.map(|(_, triangles)| triangles))));
+                        ui.end_row();
+                        ui.label("Instances:");
+                        ui.label(instance_count.to_string());
+                        ui.end_row();
+                        ui.label("File size (W × H × D):");
+                        ui.label(format!("{:.3} × {:.3} × {:.3}", model.width(), model.height(), model.depth()));
+                        ui.end_row();
+                        ui.label("Scaled size:");
+                        ui.label(format!("{:.3} × {:.3} × {:.3}", scaled.x, scaled.y, scaled.z));
+                        ui.end_row();
+                        ui.label("Bounding sphere:");
+                        ui.label(format!(
+                            "r = {:.3} at ({:.3}, {:.3}, {:.3}) in world space",
+                            sphere_radius, sphere_center.x, sphere_center.y, sphere_center.z
+                        ));
+                        ui.end_row();
+                        ui.label("Longest axis:");
+                        let longest = scaled.abs().max_element();
+                        if (longest - target).abs() <= target.abs() * 1e-3 {
+                            ui.label(format!("{:.3} ✓ matches target length", longest));
+                        } else {
+                            ui.colored_label(ui.visuals().warn_fg_color, format!("{:.3} (target length {:.3})", longest, target));
+                        }
+                        ui.end_row();
+                    });
+                    normalize = ui
+                        .button("Normalize to target length")
+                        .on_hover_text("Set the model's uniform scale so the longest scaled axis equals the target length")
+                        .clicked();
+                });
+                if normalize {
+                    let size = model.size();
+                    if self.config.model.normalize_to_target(size) {
+                        self.unsaved_changes = true;
+                    } else {
+                        self.log_message(LogLevel::Warning, "Cannot normalize: the model has no extent".to_string());
+                    }
+                }
+            }
+        }
+
+        ui.add_space(20.0);
+
+        // Enabled checkbox
+        ui.horizontal(|ui| {
+            if ui.checkbox(&mut self.config.objects[idx].enabled, "Enabled").changed() {
+                self.unsaved_changes = true;
+            }
+        });
+
+        ui.add_space(20.0);
+
+        // Delete button
+        ui.horizontal(|ui| {
+            if ui.button("🗑 Delete Object").clicked() {
+                self.pending_delete = Some(idx);
+            }
+        });
+    }
+
+    /// Record a message in the log, show it in the menu bar and echo it to the terminal
+    pub fn log_message(&mut self, level: LogLevel, text: String) {
+        match level {
+            LogLevel::Info => println!("{}", text),
+            LogLevel::Warning => eprintln!("Warning: {}", text),
+            LogLevel::Error => eprintln!("Error: {}", text),
+        }
+        self.status_message = Some((level, text.clone()));
+        self.log.push(level, text);
+    }
+
+    /// Sanitize and re-validate a newly loaded config, logging each correction and warning
+    ///
+    /// Corrections change the config, so they mark it unsaved.
+    pub fn check_config(&mut self) {
+        let fixes = self.config.sanitize();
+        if !fixes.is_empty() {
+            self.unsaved_changes = true;
+        }
+        for fix in fixes {
+            self.log_message(LogLevel::Warning, format!("Corrected {}", fix));
+        }
+
+        self.validation_warnings = self.config.validate();
+        for warning in self.validation_warnings.clone() {
+            self.log_message(LogLevel::Warning, warning);
+        }
+    }
+
+    /// Editable list of the scene's lights with add and delete buttons
+    fn render_lights(&mut self, ui: &mut Ui) {
+        let mut changed = false;
+        let mut removed = None;
+        for (i, light) in self.config.lights.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(format!("Light {} ({})", i + 1, light.kind.label()))
+                .id_salt(("light", i))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Type:");
+                        egui::ComboBox::from_id_salt(("light_kind", i))
+                            .selected_text(light.kind.label())
+                            .show_ui(ui, |ui| {
+                                for kind in LightKind::ALL {
+                                    changed |= ui.selectable_value(&mut light.kind, kind, kind.label()).changed();
+                                }
+                            });
+                        if ui.small_button("🗑").on_hover_text("Delete light").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                    if light.kind.has_position() {
+                        ui.horizontal(|ui| {
+                            ui.label("Position:");
+                            for value in &mut light.position {
+                                changed |= ui.add(DragValue::new(value).speed(0.1).max_decimals(2)).changed();
+                            }
+                        });
+                    }
+                    if light.kind.has_direction() {
+                        ui.horizontal(|ui| {
+                            ui.label("Direction:");
+                            for value in &mut light.direction {
+                                changed |= ui.add(DragValue::new(value).speed(0.01).max_decimals(2)).changed();
+                            }
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("Color:");
+                        changed |= srgb_edit(ui, &mut light.color);
+                        ui.label("Intensity:");
+                        changed |= ui.add(DragValue::new(&mut light.intensity).speed(0.05).range(0.0..=f32::MAX)).changed();
+                    });
+                    if light.kind == LightKind::Spot {
+                        ui.horizontal(|ui| {
+                            ui.label("Cone:");
+                            // Don't rewrite bad loaded angles on sight; validation already warns about them
+                            changed |= ui.add(DragValue::new(&mut light.inner_cone)
+                                .speed(0.5)
+                                .range(0.0..=light.outer_cone)
+                                .clamp_existing_to_range(false)
+                                .prefix("inner ")
+                                .suffix("°")).changed();
+                            changed |= ui.add(DragValue::new(&mut light.outer_cone)
+                                .speed(0.5)
+                                .range(light.inner_cone..=89.0)
+                                .clamp_existing_to_range(false)
+                                .prefix("outer ")
+                                .suffix("°")).changed();
+                        });
+                    }
+                });
+        }
+        if let Some(i) = removed {
+            self.config.lights.remove(i);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            ui.menu_button("➕ Add Light", |ui| {
+                for kind in LightKind::ALL {
+                    if ui.button(kind.label()).clicked() {
+                        self.config.lights.push(Light::new(kind));
+                        changed = true;
+                        ui.close_menu();
+                    }
+                }
+            });
+            if self.config.lights.len() > MAX_LIGHTS {
+                ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ Only the first {} are rendered", MAX_LIGHTS));
+            }
+        });
+        if changed {
+            self.unsaved_changes = true;
+        }
+    }
+
+    /// Compare the config with the file on disk without applying the file
+    fn show_changes(&mut self) {
+        let result = SceneConfig::load(&self.config_path).and_then(|on_disk| Ok(on_disk.diff(&self.config)?));
+        match result {
+            Ok(changes) => self.changes = Some(changes),
+            Err(e) => self.log_message(LogLevel::Error, format!("Could not compare with {}: {}", self.config_path, e)),
+        }
+    }
+
+    /// Window listing what saving would change in the file
+    fn render_changes_window(&mut self, ctx: &Context) {
+        let Some(changes) = &self.changes else {
+            return;
+        };
+        let mut open = true;
+        let mut refresh = false;
+        egui::Window::new("Changes")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Saving would make {} change(s) to {}", changes.len(), self.config_path));
+                    refresh = ui.button("🔄 Refresh").clicked();
+                });
+                ui.separator();
+                ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    if changes.is_empty() {
+                        ui.weak("No changes");
+                    }
+                    for change in changes {
+                        ui.monospace(change.to_string());
+                    }
+                });
+            });
+        if !open {
+            self.changes = None;
+        } else if refresh {
+            self.show_changes();
+        }
+    }
+
+    /// Scrollable list of log messages along the bottom of the window
+    fn render_log_panel(&mut self, ctx: &Context) {
+        egui::TopBottomPanel::bottom("log_panel")
+            .resizable(true)
+            .default_height(150.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Log");
+                    if ui.button("Clear").clicked() {
+                        self.log.clear();
+                    }
+                    if ui.button("✖").on_hover_text("Hide log console").clicked() {
+                        self.show_log = false;
+                    }
+                });
+                ui.separator();
+
+                ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        if self.log.is_empty() {
+                            ui.weak("No messages");
+                        }
+                        for entry in self.log.entries() {
+                            ui.horizontal(|ui| {
+                                ui.weak(entry.timestamp());
+                                ui.colored_label(entry.level.color(ui.visuals()), format!("{} {}", entry.level.icon(), entry.text));
+                            });
+                        }
+                    });
+            });
+    }
+
+    /// Resolved model path of the selected object, if it has one
+    fn selected_model_path(&self) -> Option<std::path::PathBuf> {
+        let obj = self.config.objects.get(self.selected_object?)?;
+        let model_path = obj.model_path.as_deref()?;
+        Some(resolve_model_path(&self.config_path, model_path))
+    }
+
+    /// Abandon the model load in flight; models loaded before stay available
+    ///
+    /// Like a failed load, the canceled model is tried again after reloading or opening a config.
+    pub fn cancel_load(&mut self) {
+        if let Some(path) = self.models.cancel() {
+            self.log_message(LogLevel::Warning, format!("Canceled loading {}", path.display()));
+        }
+    }
+
+    /// Finalize a background model load once parsing completes
+    pub fn poll_pending_load(&mut self) {
+        if let Some((path, result)) = self.models.poll() {
+            match result {
+                Ok(()) => {
+                    let triangles = self.models.get(&path).map_or(0, |model| model.triangle_count());
+                    self.log_message(LogLevel::Info, format!("Loaded {} ({} triangles)", path.display(), triangles));
+                }
+                Err(e) => self.log_message(LogLevel::Error, format!("Failed to load {}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    fn save(&mut self) {
+        match self.config.save(&self.config_path) {
+            Ok(_) => {
+                self.unsaved_changes = false;
+                self.known_modified = modified_time(&self.config_path);
+                self.file_changed_on_disk = false;
+                // The real file is current again, so the autosave is stale
+                let _ = std::fs::remove_file(autosave_path(&self.config_path));
+                self.log_message(LogLevel::Info, format!("Saved to {}", self.config_path));
+            }
+            Err(e) => {
+                self.log_message(LogLevel::Error, format!("Save failed: {}", e));
+            }
+        }
+    }
+
+    fn reload(&mut self) {
+        match SceneConfig::load(&self.config_path) {
             Ok(config) => {
                 self.config = config;
                 self.unsaved_changes = false;
-                self.selected_object = None;
-                self.status_message = Some("✓ Reloaded from file".to_string());
+                self.check_config();
+                self.select(None);
+                self.models.clear_failures();
+                self.known_modified = modified_time(&self.config_path);
+                self.file_changed_on_disk = false;
+                self.log_message(LogLevel::Info, "Reloaded from file".to_string());
             }
             Err(e) => {
-                self.status_message = Some(format!("✗ Reload failed: {}", e));
+                self.log_message(LogLevel::Error, format!("Reload failed: {}", e));
             }
         }
     }
 
     fn add_new_object(&mut self) {
         let new_obj = SceneObject {
-            name: format!("Object_{}", self.config.objects.len() + 1),
-            transform: crate::scene_data::Transform {
-                position: [0.0, 0.0, 0.0],
-                rotation_euler: [0.0, 0.0, 0.0],
-                scale: [1.0, 1.0, 1.0],
-            },
+            name: self.config.unique_name(&format!("Object_{}", self.config.objects.len() + 1)),
+            transform: Transform::default(),
             enabled: true,
+            color: [1.0, 1.0, 1.0, 1.0],
+            opacity: 1.0,
+            parent: None,
+            model_path: None,
+            tags: Vec::new(),
+            instances: None,
         };
 
         self.config.objects.push(new_obj);
-        self.selected_object = Some(self.config.objects.len() - 1);
+        self.select(Some(self.config.objects.len() - 1));
         self.unsaved_changes = true;
     }
 }
+
+/// `untitled.json` (or `untitled_N.json`) in the directory of `config_path`, not yet on disk
+pub fn untitled_path(config_path: &str) -> String {
+    let dir = std::path::Path::new(config_path).parent().unwrap_or(std::path::Path::new(""));
+    (1..)
+        .map(|n| if n == 1 { "untitled.json".to_string() } else { format!("untitled_{}.json", n) })
+        .map(|name| dir.join(name))
+        .find(|path| !path.exists())
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Three numbers separated by commas and/or spaces, optionally in brackets or parentheses
+fn parse_position(text: &str) -> Option<[f32; 3]> {
+    let inner = text.trim().trim_start_matches(['[', '(']).trim_end_matches([']', ')']);
+    let values: Vec<f32> = inner
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok().filter(|v: &f32| v.is_finite()))
+        .collect::<Option<_>>()?;
+    values.try_into().ok()
+}
+
+/// Last modification time of a file, if it can be read
+fn modified_time(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Whether an object passes the list filter; `query` must already be lowercase
+///
+/// Words of the form `tag:<name>` require that tag (case-insensitive); any other
+/// words must all appear in the name.
+fn matches_filter(obj: &SceneObject, query: &str) -> bool {
+    let name = obj.name.to_lowercase();
+    query.split_whitespace().all(|word| match word.strip_prefix("tag:") {
+        Some(tag) => obj.tags.iter().any(|t| t.to_lowercase() == tag),
+        None => name.contains(word),
+    })
+}
+
+/// Stable color for a tag, so objects sharing a first tag stand out together in the list
+///
+/// Darker shades are used on light themes so the text stays readable.
+fn tag_color(tag: &str, visuals: &egui::Visuals) -> egui::Color32 {
+    let hash = tag.to_lowercase().bytes().fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    let value = if visuals.dark_mode { 0.9 } else { 0.5 };
+    egui::ecolor::Hsva::new((hash % 360) as f32 / 360.0, 0.55, value, 1.0).into()
+}
+
+/// Read-only 4x4 matrix display, one row per line
+fn matrix_grid(ui: &mut Ui, id: &str, matrix: &Mat4) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        for row in 0..4 {
+            for value in matrix.row(row).to_array() {
+                ui.monospace(format!("{:>8.3}", value));
+            }
+            ui.end_row();
+        }
+    });
+}
+
+/// Tooltip for drag fields whose step follows the held modifiers
+const STEP_HINT: &str = "Drag to adjust. Hold Shift for coarse steps (×10), Ctrl/Cmd for fine steps (÷10)";
+
+/// DragValue speed for the held modifiers: Shift is coarse (×10), Ctrl/Cmd is fine (÷10)
+fn modifier_speed(ui: &Ui, speed: f64) -> f64 {
+    let modifiers = ui.input(|i| i.modifiers);
+    if modifiers.shift_only() {
+        // egui already slows Shift-drags by 10×, so undo that on top of the coarse factor
+        speed * 100.0
+    } else if modifiers.command_only() {
+        speed / 10.0
+    } else {
+        speed
+    }
+}
+
+/// Text box for typing an exact value or expression; returns true when a new value is committed
+///
+/// The text is applied when the box loses focus (Enter or clicking away). Esc or
+/// an unparseable entry reverts to the current value.
+///
+/// `id_salt` must differ between fields: sibling rows share a parent ID, so it
+/// is what keeps one box's half-typed text out of the others.
+fn exact_field(ui: &mut Ui, id_salt: impl std::hash::Hash, value: &mut f32) -> bool {
+    let id = ui.make_persistent_id(id_salt);
+    let mut text = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_else(|| value.to_string());
+    let valid = eval_expression(&text).is_some();
+    let mut edit = egui::TextEdit::singleline(&mut text).id(id).desired_width(90.0);
+    if !valid {
+        edit = edit.text_color(ui.visuals().error_fg_color);
+    }
+    let response = ui.add(edit);
+
+    if response.has_focus() {
+        ui.data_mut(|d| d.insert_temp(id, text));
+        return false;
+    }
+    if !response.lost_focus() {
+        return false;
+    }
+    ui.data_mut(|d| d.remove::<String>(id));
+    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+        return false;
+    }
+    match eval_expression(&text).map(|v| v as f32) {
+        Some(parsed) if parsed.is_finite() && parsed != *value => {
+            *value = parsed;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Color button for an sRGB color with alpha from the config
+///
+/// egui's float pickers take linear values, so the color is converted for the
+/// picker and only written back when edited, keeping untouched colors exact.
+fn srgba_edit(ui: &mut Ui, color: &mut [f32; 4]) -> bool {
+    let mut linear = srgb_to_linear(*color);
+    let changed = ui.color_edit_button_rgba_unmultiplied(&mut linear).changed();
+    if changed {
+        *color = linear_to_srgb(linear);
+    }
+    changed
+}
+
+/// Color button for an sRGB color without alpha, see `srgba_edit`
+fn srgb_edit(ui: &mut Ui, color: &mut [f32; 3]) -> bool {
+    let mut linear = srgb_to_linear(*color);
+    let changed = ui.color_edit_button_rgb(&mut linear).changed();
+    if changed {
+        *color = linear_to_srgb(linear);
+    }
+    changed
+}
+
+/// 🔒/🔓 toggle for an axis lock
+fn lock_toggle(ui: &mut Ui, locked: &mut bool) {
+    let icon = if *locked { "🔒" } else { "🔓" };
+    let hint = if *locked { "Unlock this axis" } else { "Lock this axis against edits, resets, nudges, paste, mirror, snap, align and CSV import" };
+    if ui.selectable_label(*locked, icon).on_hover_text(hint).clicked() {
+        *locked = !*locked;
+    }
+}
+
+/// Set every unlocked axis of `values` to `default`
+fn reset_unlocked(values: &mut [f32; 3], default: [f32; 3], locks: [bool; 3]) {
+    for ((value, default), locked) in values.iter_mut().zip(default).zip(locks) {
+        if !locked {
+            *value = default;
+        }
+    }
+}
+
+/// DragValue suffix for an angle unit
+fn angle_suffix(unit: &str) -> &'static str {
+    if unit == "radians" { " rad" } else { "°" }
+}
+
+/// DragValue speed giving a similar feel in either angle unit
+fn angle_speed(unit: &str) -> f64 {
+    if unit == "radians" { 0.01 } else { 1.0 }
+}
+
+/// Angle unit dropdown; returns the newly picked unit, if any
+fn unit_combo(ui: &mut Ui, id: &str, current: &str) -> Option<&'static str> {
+    let mut picked = None;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(current)
+        .show_ui(ui, |ui| {
+            for unit in ANGLE_UNITS {
+                if ui.selectable_label(current == unit, unit).clicked() && current != unit {
+                    picked = Some(unit);
+                }
+            }
+        });
+    picked
+}
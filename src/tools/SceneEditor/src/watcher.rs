@@ -0,0 +1,71 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+/// How long the file must stay quiet before a change is reported
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches the config file for edits made outside the editor
+///
+/// The parent directory is watched rather than the file itself so editors that
+/// save by writing a temporary file and renaming it over the original are seen.
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    file_name: PathBuf,
+    last_event: Option<Instant>,
+}
+
+impl ConfigWatcher {
+    pub fn new(config_path: &str, ctx: &egui::Context) -> notify::Result<Self> {
+        let path = Path::new(config_path);
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let file_name = PathBuf::from(path.file_name().unwrap_or_default());
+
+        let (sender, events) = mpsc::channel();
+        let ctx = ctx.clone();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(event);
+            ctx.request_repaint();
+        })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            file_name,
+            last_event: None,
+        })
+    }
+
+    /// Whether the file changed, once events have been quiet for the debounce period
+    ///
+    /// While a change is still settling, returns false and asks for a repaint so the
+    /// check runs again.
+    pub fn poll(&mut self, ctx: &egui::Context) -> bool {
+        for event in self.events.try_iter().flatten() {
+            if event.kind.is_access() {
+                continue;
+            }
+            if event.paths.iter().any(|p| p.file_name() == Some(self.file_name.as_os_str())) {
+                self.last_event = Some(Instant::now());
+            }
+        }
+
+        match self.last_event {
+            Some(at) if at.elapsed() >= DEBOUNCE => {
+                self.last_event = None;
+                true
+            }
+            Some(at) => {
+                ctx.request_repaint_after(DEBOUNCE - at.elapsed());
+                false
+            }
+            None => false,
+        }
+    }
+}